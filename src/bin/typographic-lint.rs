@@ -0,0 +1,304 @@
+extern crate clap;
+extern crate glob;
+extern crate typographic_linter;
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::rc::Rc;
+
+use clap::Parser;
+use glob::Pattern;
+use typographic_linter::config::CONFIG_FILE_NAME;
+use typographic_linter::output::FileWarnings;
+use typographic_linter::{Config, InputFormat, Linter, Locale, OutputFormat};
+
+const STDIN_PATH: &str = "-";
+
+/// Checks files, directories or the standard input for typographic warnings.
+#[derive(Parser)]
+#[command(name = "typographic-lint", version)]
+struct Options {
+    /// Files or directories to lint, `-` for the standard input (default).
+    paths: Vec<PathBuf>,
+
+    /// Locale of the linted texts [default: locale of the catalogue, configured locale, or `en`].
+    #[arg(short, long)]
+    locale: Option<String>,
+
+    /// Applies the fixes in place (or prints the fixed standard input).
+    #[arg(long)]
+    fix: bool,
+
+    /// Format of the linted files: `text`, `markdown`, `html`, `latex`, `po`, `xliff` or `json` [default:
+    /// guessed from the file extension].
+    #[arg(long, value_name = "FORMAT")]
+    input_format: Option<InputFormat>,
+
+    /// Output format of the warnings: `text`, `json`, `sarif`, `checkstyle`, `junit` or `gitlab`.
+    #[arg(short, long, value_name = "FORMAT", default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+
+    /// Only lints the files matching this glob when walking directories.
+    #[arg(long, value_name = "GLOB")]
+    include: Vec<Pattern>,
+
+    /// Skips the files and directories matching this glob.
+    #[arg(long, value_name = "GLOB")]
+    exclude: Vec<Pattern>,
+
+    /// Configuration file to use instead of the discovered `typographic-linter.toml` files.
+    #[arg(long, value_name = "FILE", conflicts_with = "no_config")]
+    config: Option<PathBuf>,
+
+    /// Ignores the `typographic-linter.toml` files.
+    #[arg(long)]
+    no_config: bool,
+}
+
+struct Runner {
+    options: Options,
+    /// Configurations loaded so far, by path.
+    configs: HashMap<PathBuf, Rc<Config>>,
+    /// Linters built so far, by configuration directory and locale.
+    linters: HashMap<(Option<PathBuf>, String), Rc<Linter>>,
+    has_errors: bool,
+}
+
+fn main() {
+    let options = Options::parse();
+
+    let mut runner = Runner {
+        options,
+        configs: HashMap::new(),
+        linters: HashMap::new(),
+        has_errors: false,
+    };
+
+    let mut paths = runner.options.paths.clone();
+
+    if paths.is_empty() {
+        paths.push(PathBuf::from(STDIN_PATH));
+    }
+
+    let mut files = Vec::<PathBuf>::new();
+
+    for path in paths {
+        if let Err(error) = runner.collect_files(&path, true, &mut files) {
+            runner.error(&path, error);
+        }
+    }
+
+    let mut results = Vec::<FileWarnings>::new();
+
+    for file in files {
+        match runner.lint(&file) {
+            Ok(warnings) => results.push(warnings),
+            Err(error) => runner.error(&file, error),
+        }
+    }
+
+    runner.print_results(&results);
+
+    if runner.has_errors {
+        process::exit(2);
+    } else if results.iter().any(|result| !result.warnings.is_empty()) {
+        process::exit(1);
+    }
+}
+
+/// Returns whether a glob matches the path, or the path relative to one of its ancestors, so that
+/// `vendor/*` matches `docs/vendor/x.md` as well as `vendor/x.md`.
+fn matches_any(patterns: &[Pattern], path: &Path) -> bool {
+    let components = path.components().collect::<Vec<_>>();
+
+    (0..components.len()).any(|start| {
+        let suffix = components[start..].iter().collect::<PathBuf>();
+
+        patterns.iter().any(|pattern| pattern.matches_path(&suffix))
+    })
+}
+
+impl Runner {
+    fn error(&mut self, path: &Path, error: io::Error) {
+        eprintln!("typographic-lint: {}: {}", path.display(), error);
+        self.has_errors = true;
+    }
+
+    fn config(&mut self, path: &Path) -> io::Result<Option<Rc<Config>>> {
+        if self.options.no_config {
+            return Ok(None);
+        }
+
+        let config_path = match self.options.config {
+            Some(ref config_path) => config_path.clone(),
+            None => {
+                let path = if path == Path::new(STDIN_PATH) { Path::new(".") } else { path };
+
+                match Config::find(path)? {
+                    Some(config_path) => config_path,
+                    None => return Ok(None),
+                }
+            }
+        };
+
+        if !self.configs.contains_key(&config_path) {
+            let config = Config::load(&config_path)
+                .map_err(|error| io::Error::other(format!("{}: {}", config_path.display(), error)))?;
+
+            self.configs.insert(config_path.clone(), Rc::new(config));
+        }
+
+        Ok(self.configs.get(&config_path).cloned())
+    }
+
+    fn collect_files(&mut self, path: &Path, explicit: bool, files: &mut Vec<PathBuf>) -> io::Result<()> {
+        if path == Path::new(STDIN_PATH) {
+            files.push(path.to_path_buf());
+            return Ok(());
+        }
+
+        if matches_any(&self.options.exclude, path) {
+            return Ok(());
+        }
+
+        if let Some(config) = self.config(path)? {
+            if config.is_ignored(path) {
+                return Ok(());
+            }
+        }
+
+        if path.is_dir() {
+            let mut entries = fs::read_dir(path)?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<io::Result<Vec<PathBuf>>>()?;
+
+            entries.sort();
+
+            for entry in entries {
+                // Hidden files and directories (`.git`, …) are never walked into, and configuration
+                // files aren’t prose.
+                let is_skipped = entry
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with('.') || name == CONFIG_FILE_NAME);
+
+                if !is_skipped {
+                    self.collect_files(&entry, false, files)?;
+                }
+            }
+        } else if explicit
+            || self.options.include.is_empty()
+            || matches_any(&self.options.include, path)
+        {
+            files.push(path.to_path_buf());
+        }
+
+        Ok(())
+    }
+
+    fn linter(&mut self, path: &Path, catalogue_locale: Option<&str>) -> io::Result<Rc<Linter>> {
+        let config = self.config(path)?;
+
+        let locale = self
+            .options
+            .locale
+            .as_deref()
+            .or(catalogue_locale)
+            .or_else(|| config.as_ref().and_then(|config| config.locale_for(path)))
+            .unwrap_or("en")
+            .to_string();
+        let key = (config.as_ref().map(|config| config.root.clone()), locale);
+
+        if !self.linters.contains_key(&key) {
+            let builder = match config {
+                Some(ref config) => Linter::builder().config(config),
+                None => Linter::builder(),
+            };
+
+            let linter = builder
+                .locale(&key.1)
+                .build()
+                .map_err(|error| io::Error::other(error.to_string()))?;
+
+            self.linters.insert(key.clone(), Rc::new(linter));
+        }
+
+        Ok(self.linters[&key].clone())
+    }
+
+    fn lint(&mut self, path: &Path) -> io::Result<FileWarnings> {
+        let is_stdin = path == Path::new(STDIN_PATH);
+        let display_path = if is_stdin { "<stdin>".to_string() } else { path.display().to_string() };
+
+        let mut text = String::new();
+
+        if is_stdin {
+            io::stdin().read_to_string(&mut text)?;
+        } else {
+            text = fs::read_to_string(path)?;
+        }
+
+        let format = self.options.input_format.unwrap_or_else(|| InputFormat::from_path(path));
+        let catalogue_locale = format.locale(&text, if is_stdin { None } else { Some(path) });
+
+        // The catalogues of a project are usually translated in many locales, some of which can't
+        // be linted.
+        if self.options.locale.is_none() && catalogue_locale.as_ref().is_some_and(|locale| locale.parse::<Locale>().is_err()) {
+            return Ok(FileWarnings { path: display_path, warnings: Vec::new(), text });
+        }
+
+        let linter = self.linter(path, catalogue_locale.as_deref())?;
+
+        if self.options.fix {
+            let fixed = linter.fix_as(&text, format);
+
+            if is_stdin {
+                io::stdout().write_all(fixed.as_bytes())?;
+            } else if fixed != text {
+                fs::write(path, &fixed)?;
+            }
+
+            text = fixed;
+        }
+
+        Ok(FileWarnings {
+            path: display_path,
+            warnings: linter.check_as(&text, format).warnings,
+            text,
+        })
+    }
+
+    fn print_results(&self, results: &[FileWarnings]) {
+        // The fixed standard input is written to the standard output, so the warnings left can
+        // only be reported on the error output.
+        let mut output: Box<dyn Write> = if self.options.fix && results.iter().any(|result| result.path == "<stdin>") {
+            Box::new(io::stderr())
+        } else {
+            Box::new(io::stdout())
+        };
+
+        if let Err(error) = self.options.format.write(&mut output, results) {
+            eprintln!("typographic-lint: {}", error);
+            process::exit(2);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_any() {
+        let patterns = vec![Pattern::new("vendor/*").unwrap(), Pattern::new("*.min.md").unwrap()];
+
+        assert!(matches_any(&patterns, Path::new("vendor/x.md")));
+        assert!(matches_any(&patterns, Path::new("docs/vendor/x.md")));
+        assert!(matches_any(&patterns, Path::new("./docs/a/b.min.md")));
+        assert!(!matches_any(&patterns, Path::new("docs/vendors/x.md")));
+        assert!(!matches_any(&patterns, Path::new("docs/x.md")));
+    }
+}
@@ -0,0 +1,672 @@
+extern crate lsp_server;
+extern crate lsp_types;
+extern crate serde;
+#[macro_use] extern crate serde_derive;
+extern crate serde_json;
+extern crate typographic_linter;
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, RequestId, Response};
+use lsp_types::notification::{
+    DidChangeConfiguration, DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument,
+    Initialized, Notification as _, PublishDiagnostics, ShowMessage,
+};
+use lsp_types::request::{CodeActionRequest, Request as _, WorkspaceConfiguration};
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOptions, CodeActionOrCommand, CodeActionParams,
+    CodeActionProviderCapability, ConfigurationItem, ConfigurationParams, Diagnostic, DiagnosticSeverity,
+    DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams, DidSaveTextDocumentParams,
+    InitializeParams, MessageType, NumberOrString, Position, PublishDiagnosticsParams, Range,
+    ServerCapabilities, ShowMessageParams, TextDocumentSyncCapability, TextDocumentSyncKind,
+    TextDocumentSyncOptions, TextDocumentSyncSaveOptions, TextEdit, Url, WorkspaceEdit,
+};
+use serde::de::DeserializeOwned;
+use typographic_linter::config::CONFIG_FILE_NAME;
+use typographic_linter::errors::{self, LineIndex, LinterFix, LinterWarning, Severity};
+use typographic_linter::{Config, InputFormat, Linter, Locale};
+
+type ServerResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
+
+/// Section of the client configuration holding the settings.
+const SETTINGS_SECTION: &str = "typographicLinter";
+
+/// Settings sent by the client, in its initialization options or under the `typographicLinter`
+/// section of its configuration.
+#[derive(Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct Settings {
+    /// Locale of the documents without a configured or declared one [default: `en`].
+    locale: Option<String>,
+}
+
+impl Settings {
+    /// Reads the initialization options, which hold the settings with or without their section.
+    fn from_value(value: serde_json::Value) -> Settings {
+        Settings::from_section(value.get(SETTINGS_SECTION))
+            .or_else(|| serde_json::from_value(value).ok())
+            .unwrap_or_default()
+    }
+
+    /// Reads the section of a client configuration, or returns `None` if it has none (ex: `null`).
+    fn from_section(section: Option<&serde_json::Value>) -> Option<Settings> {
+        section
+            .filter(|section| !section.is_null())
+            .and_then(|section| serde_json::from_value(section.clone()).ok())
+    }
+}
+
+/// An open document, as last sent by the client.
+struct Document {
+    text: String,
+    version: i32,
+    format: InputFormat,
+}
+
+/// The settings, configurations and linters shared by the documents of a workspace.
+#[derive(Default)]
+struct Workspace {
+    /// Settings of the client, for all the workspace folders.
+    settings: Settings,
+    /// Settings of the workspace folders that have their own, by folder path.
+    folder_settings: HashMap<PathBuf, Settings>,
+    /// Configurations loaded so far, by path.
+    configs: HashMap<PathBuf, Rc<Config>>,
+    /// Linters built so far, by configuration directory and locale.
+    linters: HashMap<(Option<PathBuf>, String), Rc<Linter>>,
+}
+
+impl Workspace {
+    /// Returns the linter of a document, or `None` when the document shouldn't be linted.
+    fn linter(&mut self, path: Option<&Path>, document: &Document) -> Result<Option<Rc<Linter>>, String> {
+        let config = match path {
+            Some(path) => self.config(path)?,
+            None => None,
+        };
+
+        if let (Some(config), Some(path)) = (&config, path) {
+            if config.is_ignored(path) {
+                return Ok(None);
+            }
+        }
+
+        let catalogue_locale = document.format.locale(&document.text, path);
+
+        // The catalogues of a project are usually translated in many locales, some of which can't
+        // be linted.
+        if catalogue_locale.as_ref().is_some_and(|locale| locale.parse::<Locale>().is_err()) {
+            return Ok(None);
+        }
+
+        let locale = catalogue_locale
+            .as_deref()
+            .or_else(|| config.as_ref().zip(path).and_then(|(config, path)| config.locale_for(path)))
+            .or(self.locale(path))
+            .unwrap_or("en")
+            .to_string();
+        let key = (config.as_ref().map(|config| config.root.clone()), locale);
+
+        if !self.linters.contains_key(&key) {
+            let builder = match config {
+                Some(ref config) => Linter::builder().config(config),
+                None => Linter::builder(),
+            };
+
+            let linter = builder.locale(&key.1).build().map_err(|error| error.to_string())?;
+
+            self.linters.insert(key.clone(), Rc::new(linter));
+        }
+
+        Ok(Some(self.linters[&key].clone()))
+    }
+
+    fn config(&mut self, path: &Path) -> Result<Option<Rc<Config>>, String> {
+        let config_path = match Config::find(path) {
+            Ok(Some(config_path)) => config_path,
+            // Unsaved documents have no configuration.
+            Ok(None) | Err(_) => return Ok(None),
+        };
+
+        if !self.configs.contains_key(&config_path) {
+            let config =
+                Config::load(&config_path).map_err(|error| format!("{}: {}", config_path.display(), error))?;
+
+            self.configs.insert(config_path.clone(), Rc::new(config));
+        }
+
+        Ok(self.configs.get(&config_path).cloned())
+    }
+
+    /// Returns the locale set for the innermost workspace folder of a document, or for the client.
+    fn locale(&self, path: Option<&Path>) -> Option<&str> {
+        let folder_locale = self
+            .folder_settings
+            .iter()
+            .filter(|(folder, settings)| settings.locale.is_some() && path.is_some_and(|path| path.starts_with(folder)))
+            .max_by_key(|(folder, _)| folder.components().count())
+            .and_then(|(_, settings)| settings.locale.as_deref());
+
+        folder_locale.or(self.settings.locale.as_deref())
+    }
+
+    /// Forgets the configurations and linters, to pick up their changes.
+    fn reset(&mut self) {
+        self.configs.clear();
+        self.linters.clear();
+    }
+}
+
+struct Server {
+    connection: Connection,
+    documents: HashMap<Url, Document>,
+    workspace: Workspace,
+    /// Workspace folders, whose settings are pulled from the client if it supports it.
+    folders: Vec<Url>,
+    can_pull_settings: bool,
+    /// Pending request for the settings of the workspace folders.
+    settings_request: Option<RequestId>,
+    next_request_id: i32,
+    /// Last error shown to the user, so that it isn’t shown again on each change.
+    last_error: Option<String>,
+}
+
+fn main() -> ServerResult<()> {
+    let (connection, io_threads) = Connection::stdio();
+
+    Server::initialize(connection)?.run()?;
+    io_threads.join()?;
+
+    Ok(())
+}
+
+impl Server {
+    fn initialize(connection: Connection) -> ServerResult<Server> {
+        let capabilities = ServerCapabilities {
+            text_document_sync: Some(TextDocumentSyncCapability::Options(TextDocumentSyncOptions {
+                open_close: Some(true),
+                change: Some(TextDocumentSyncKind::FULL),
+                save: Some(TextDocumentSyncSaveOptions::Supported(true)),
+                ..Default::default()
+            })),
+            code_action_provider: Some(CodeActionProviderCapability::Options(CodeActionOptions {
+                code_action_kinds: Some(vec![CodeActionKind::QUICKFIX, CodeActionKind::SOURCE_FIX_ALL]),
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+
+        let params = connection.initialize(serde_json::to_value(capabilities)?)?;
+        let params: InitializeParams = serde_json::from_value(params)?;
+
+        Ok(Server {
+            connection,
+            documents: HashMap::new(),
+            workspace: Workspace {
+                settings: params.initialization_options.map(Settings::from_value).unwrap_or_default(),
+                ..Default::default()
+            },
+            folders: params.workspace_folders.unwrap_or_default().into_iter().map(|folder| folder.uri).collect(),
+            can_pull_settings: params
+                .capabilities
+                .workspace
+                .and_then(|workspace| workspace.configuration)
+                .unwrap_or(false),
+            settings_request: None,
+            next_request_id: 1,
+            last_error: None,
+        })
+    }
+
+    fn run(mut self) -> ServerResult<()> {
+        let receiver = self.connection.receiver.clone();
+
+        for message in &receiver {
+            match message {
+                Message::Request(request) => {
+                    if self.connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+
+                    self.handle_request(request)?;
+                },
+                Message::Notification(notification) => self.handle_notification(notification)?,
+                Message::Response(response) => self.handle_response(response)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_request(&mut self, request: Request) -> ServerResult<()> {
+        let response = match request.method.as_str() {
+            CodeActionRequest::METHOD => match serde_json::from_value::<CodeActionParams>(request.params) {
+                Ok(params) => Response::new_ok(request.id, self.code_actions(&params)),
+                Err(error) => Response::new_err(request.id, ErrorCode::InvalidParams as i32, error.to_string()),
+            },
+            _ => Response::new_err(
+                request.id,
+                ErrorCode::MethodNotFound as i32,
+                format!("Unsupported request: {}", request.method),
+            ),
+        };
+
+        self.send(response)
+    }
+
+    fn handle_response(&mut self, response: Response) -> ServerResult<()> {
+        if self.settings_request.as_ref() != Some(&response.id) {
+            return Ok(());
+        }
+
+        self.settings_request = None;
+
+        // Folders without settings of their own use the ones of the client.
+        let sections = response
+            .result
+            .and_then(|result| serde_json::from_value::<Vec<serde_json::Value>>(result).ok())
+            .unwrap_or_default();
+
+        self.workspace.folder_settings = self
+            .folders
+            .iter()
+            .zip(sections)
+            .filter_map(|(folder, section)| {
+                Some((folder.to_file_path().ok()?, Settings::from_section(Some(&section))?))
+            })
+            .collect();
+        self.workspace.reset();
+        self.publish_all_diagnostics()
+    }
+
+    /// Asks the client for the settings of each workspace folder.
+    fn pull_settings(&mut self) -> ServerResult<()> {
+        if !self.can_pull_settings || self.folders.is_empty() {
+            return Ok(());
+        }
+
+        let items = self
+            .folders
+            .iter()
+            .map(|folder| ConfigurationItem {
+                scope_uri: Some(folder.clone()),
+                section: Some(SETTINGS_SECTION.to_string()),
+            })
+            .collect();
+        let id = RequestId::from(self.next_request_id);
+
+        self.next_request_id += 1;
+        self.settings_request = Some(id.clone());
+        self.connection.sender.send(Message::Request(Request::new(
+            id,
+            WorkspaceConfiguration::METHOD.to_string(),
+            ConfigurationParams { items },
+        )))?;
+
+        Ok(())
+    }
+
+    fn handle_notification(&mut self, notification: Notification) -> ServerResult<()> {
+        match notification.method.as_str() {
+            Initialized::METHOD => self.pull_settings(),
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams = params(notification)?;
+                let document = params.text_document;
+
+                let format = document
+                    .uri
+                    .to_file_path()
+                    .ok()
+                    .map(|path| InputFormat::from_path(&path))
+                    .filter(|format| *format != InputFormat::Text)
+                    .or_else(|| document.language_id.parse().ok())
+                    .unwrap_or_default();
+
+                self.documents.insert(
+                    document.uri.clone(),
+                    Document { text: document.text, version: document.version, format },
+                );
+                self.publish_diagnostics(&document.uri)
+            },
+            DidChangeTextDocument::METHOD => {
+                let params: DidChangeTextDocumentParams = params(notification)?;
+
+                // Documents are synchronized in full, so the last change holds the whole text.
+                if let (Some(document), Some(change)) =
+                    (self.documents.get_mut(&params.text_document.uri), params.content_changes.into_iter().last())
+                {
+                    document.text = change.text;
+                    document.version = params.text_document.version;
+                }
+
+                self.publish_diagnostics(&params.text_document.uri)
+            },
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams = params(notification)?;
+
+                self.documents.remove(&params.text_document.uri);
+                self.notify::<PublishDiagnostics>(PublishDiagnosticsParams::new(
+                    params.text_document.uri,
+                    Vec::new(),
+                    None,
+                ))
+            },
+            DidSaveTextDocument::METHOD => {
+                let params: DidSaveTextDocumentParams = params(notification)?;
+
+                if params.text_document.uri.path().ends_with(CONFIG_FILE_NAME) {
+                    self.workspace.reset();
+                    self.publish_all_diagnostics()?;
+                }
+
+                Ok(())
+            },
+            DidChangeConfiguration::METHOD => {
+                // Clients pulling the settings send none here: the previous ones are kept.
+                let settings = notification.params.get("settings").map(|settings| settings.get(SETTINGS_SECTION));
+
+                if let Some(settings) = settings.and_then(Settings::from_section) {
+                    self.workspace.settings = settings;
+                }
+
+                self.pull_settings()?;
+                self.workspace.reset();
+                self.publish_all_diagnostics()
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks a document, showing the configuration errors to the user.
+    fn check(&mut self, uri: &Url) -> Vec<LinterWarning> {
+        let document = match self.documents.get(uri) {
+            Some(document) => document,
+            None => return Vec::new(),
+        };
+
+        let path = uri.to_file_path().ok();
+
+        let (warnings, error) = match self.workspace.linter(path.as_deref(), document) {
+            Ok(Some(linter)) => (linter.check_as(&document.text, document.format).warnings, None),
+            Ok(None) => (Vec::new(), None),
+            Err(error) => (Vec::new(), Some(error)),
+        };
+
+        if error.is_some() && error != self.last_error {
+            let message = format!("typographic-linter: {}", error.as_deref().unwrap_or_default());
+
+            // The diagnostics are still published if the error can’t be shown.
+            let _ = self.notify::<ShowMessage>(ShowMessageParams { typ: MessageType::ERROR, message });
+        }
+
+        self.last_error = error;
+
+        warnings
+    }
+
+    fn publish_diagnostics(&mut self, uri: &Url) -> ServerResult<()> {
+        let diagnostics = self.check(uri).iter().map(diagnostic).collect();
+        let version = self.documents.get(uri).map(|document| document.version);
+
+        self.notify::<PublishDiagnostics>(PublishDiagnosticsParams::new(uri.clone(), diagnostics, version))
+    }
+
+    fn publish_all_diagnostics(&mut self) -> ServerResult<()> {
+        let uris = self.documents.keys().cloned().collect::<Vec<Url>>();
+
+        for uri in uris {
+            self.publish_diagnostics(&uri)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns a quick fix for each warning in the range, and an action fixing the whole document.
+    fn code_actions(&mut self, params: &CodeActionParams) -> Vec<CodeActionOrCommand> {
+        let uri = &params.text_document.uri;
+        let warnings = self.check(uri);
+
+        let text = match self.documents.get(uri) {
+            Some(document) => &document.text,
+            None => return Vec::new(),
+        };
+
+        let index = LineIndex::new(text);
+
+        let mut actions = warnings
+            .iter()
+            .filter(|warning| !warning.fixes.is_empty())
+            .filter(|warning| {
+                let diagnostic = diagnostic(warning);
+
+                diagnostic.range.start <= params.range.end && params.range.start <= diagnostic.range.end
+            })
+            .map(|warning| {
+                CodeActionOrCommand::CodeAction(CodeAction {
+                    title: format!("Fix: {}", warning.message),
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![diagnostic(warning)]),
+                    edit: Some(workspace_edit(uri, &index, &warning.fixes)),
+                    is_preferred: Some(true),
+                    ..Default::default()
+                })
+            })
+            .collect::<Vec<CodeActionOrCommand>>();
+
+        if !actions.is_empty() {
+            let fixes = typographic_linter::fixes::apply_fixes(text, &warnings).applied;
+
+            actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                title: "Fix all typographic warnings".to_string(),
+                kind: Some(CodeActionKind::SOURCE_FIX_ALL),
+                edit: Some(workspace_edit(uri, &index, &fixes)),
+                ..Default::default()
+            }));
+        }
+
+        actions
+    }
+
+    fn notify<N: lsp_types::notification::Notification>(&self, params: N::Params) -> ServerResult<()> {
+        self.connection
+            .sender
+            .send(Message::Notification(Notification::new(N::METHOD.to_string(), params)))?;
+
+        Ok(())
+    }
+
+    fn send(&self, response: Response) -> ServerResult<()> {
+        self.connection.sender.send(Message::Response(response))?;
+
+        Ok(())
+    }
+}
+
+fn params<P: DeserializeOwned>(notification: Notification) -> ServerResult<P> {
+    Ok(serde_json::from_value(notification.params)?)
+}
+
+fn diagnostic(warning: &LinterWarning) -> Diagnostic {
+    let message = match warning.entry {
+        Some(ref entry) => format!("{} (entry: {})", warning.message, entry),
+        None => warning.message.clone(),
+    };
+
+    Diagnostic {
+        range: Range::new(position(warning.start_position), position(warning.end_position)),
+        severity: Some(match warning.severity {
+            Severity::Error => DiagnosticSeverity::ERROR,
+            Severity::Warning => DiagnosticSeverity::WARNING,
+            Severity::Info => DiagnosticSeverity::INFORMATION,
+        }),
+        code: Some(NumberOrString::String(warning.rule.clone())),
+        source: Some("typographic-linter".to_string()),
+        message,
+        ..Default::default()
+    }
+}
+
+/// Converts a position of the linter to a 0-based LSP one, counted in UTF-16 code units.
+fn position(position: errors::Position) -> Position {
+    Position::new(position.line as u32 - 1, position.utf16_column as u32 - 1)
+}
+
+fn workspace_edit(uri: &Url, index: &LineIndex, fixes: &[LinterFix]) -> WorkspaceEdit {
+    let edits = fixes
+        .iter()
+        .map(|fix| TextEdit {
+            range: Range::new(position(index.position(fix.start)), position(index.position(fix.end))),
+            new_text: fix.replacement.clone(),
+        })
+        .collect();
+
+    WorkspaceEdit { changes: Some(HashMap::from([(uri.clone(), edits)])), ..Default::default() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::request::Initialize;
+    use lsp_types::{
+        CodeActionContext, DidChangeConfigurationParams, PartialResultParams, TextDocumentIdentifier, TextDocumentItem,
+        WorkDoneProgressParams,
+    };
+    use std::thread;
+
+    fn request<R: lsp_types::request::Request>(id: i32, params: R::Params) -> Message {
+        Message::Request(Request::new(id.into(), R::METHOD.to_string(), params))
+    }
+
+    fn notification<N: lsp_types::notification::Notification>(params: N::Params) -> Message {
+        Message::Notification(Notification::new(N::METHOD.to_string(), params))
+    }
+
+    #[test]
+    fn test_server() {
+        let (server, client) = Connection::memory();
+        let server = thread::spawn(move || Server::initialize(server).unwrap().run().unwrap());
+
+        let uri = Url::parse("untitled:note").unwrap();
+
+        client
+            .sender
+            .send(request::<Initialize>(
+                1,
+                InitializeParams {
+                    initialization_options: Some(serde_json::json!({ "locale": "fr" })),
+                    ..Default::default()
+                },
+            ))
+            .unwrap();
+        client.receiver.recv().unwrap();
+        client
+            .sender
+            .send(notification::<lsp_types::notification::Initialized>(lsp_types::InitializedParams {}))
+            .unwrap();
+        client
+            .sender
+            .send(notification::<DidOpenTextDocument>(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem::new(
+                    uri.clone(),
+                    "plaintext".to_string(),
+                    1,
+                    "C’est “moi”.\nOui!".to_string(),
+                ),
+            }))
+            .unwrap();
+
+        let published = match client.receiver.recv().unwrap() {
+            Message::Notification(notification) => {
+                notification.extract::<PublishDiagnosticsParams>(PublishDiagnostics::METHOD).unwrap()
+            },
+            message => panic!("unexpected message: {:?}", message),
+        };
+
+        assert_eq!(Some(1), published.version);
+        assert_eq!(
+            vec!["quotes", "space-before-double-punctuation"],
+            published
+                .diagnostics
+                .iter()
+                .map(|diagnostic| match diagnostic.code {
+                    Some(NumberOrString::String(ref rule)) => rule.as_str(),
+                    _ => "",
+                })
+                .collect::<Vec<&str>>()
+        );
+        assert_eq!(Range::new(Position::new(1, 2), Position::new(1, 4)), published.diagnostics[1].range);
+
+        client
+            .sender
+            .send(request::<CodeActionRequest>(
+                2,
+                CodeActionParams {
+                    text_document: TextDocumentIdentifier::new(uri.clone()),
+                    range: Range::new(Position::new(1, 3), Position::new(1, 3)),
+                    context: CodeActionContext::default(),
+                    work_done_progress_params: WorkDoneProgressParams::default(),
+                    partial_result_params: PartialResultParams::default(),
+                },
+            ))
+            .unwrap();
+
+        let actions = match client.receiver.recv().unwrap() {
+            Message::Response(response) => {
+                serde_json::from_value::<Vec<CodeAction>>(response.result.unwrap()).unwrap()
+            },
+            message => panic!("unexpected message: {:?}", message),
+        };
+
+        assert_eq!(2, actions.len());
+        assert_eq!(
+            vec![TextEdit { range: Range::new(Position::new(1, 3), Position::new(1, 3)), new_text: "\u{a0}".to_string() }],
+            actions[0].edit.as_ref().unwrap().changes.as_ref().unwrap()[&uri]
+        );
+        assert_eq!(Some(CodeActionKind::SOURCE_FIX_ALL), actions[1].kind);
+
+        // Settings left out of a change are kept.
+        client
+            .sender
+            .send(notification::<DidChangeConfiguration>(DidChangeConfigurationParams {
+                settings: serde_json::Value::Null,
+            }))
+            .unwrap();
+
+        let published = match client.receiver.recv().unwrap() {
+            Message::Notification(notification) => {
+                notification.extract::<PublishDiagnosticsParams>(PublishDiagnostics::METHOD).unwrap()
+            },
+            message => panic!("unexpected message: {:?}", message),
+        };
+
+        assert_eq!(2, published.diagnostics.len());
+
+        client.sender.send(request::<lsp_types::request::Shutdown>(3, ())).unwrap();
+        client.receiver.recv().unwrap();
+        client.sender.send(notification::<lsp_types::notification::Exit>(())).unwrap();
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_workspace_settings() {
+        let mut workspace = Workspace {
+            settings: Settings { locale: Some("fr".to_string()) },
+            ..Default::default()
+        };
+
+        workspace.folder_settings.insert(PathBuf::from("/work"), Settings { locale: Some("es".to_string()) });
+        workspace.folder_settings.insert(PathBuf::from("/work/de"), Settings { locale: Some("de".to_string()) });
+        workspace.folder_settings.insert(PathBuf::from("/work/en"), Settings::default());
+
+        assert_eq!(Some("de"), workspace.locale(Some(Path::new("/work/de/notes.md"))));
+        assert_eq!(Some("es"), workspace.locale(Some(Path::new("/work/en/notes.md"))));
+        assert_eq!(Some("fr"), workspace.locale(Some(Path::new("/home/notes.md"))));
+        assert_eq!(Some("fr"), workspace.locale(None));
+
+        assert!(Settings::from_section(Some(&serde_json::Value::Null)).is_none());
+        assert!(Settings::from_section(serde_json::json!({ "editor": { "tabSize": 2 } }).get(SETTINGS_SECTION)).is_none());
+        assert_eq!(Some("de".to_string()), Settings::from_value(serde_json::json!({ "locale": "de" })).locale);
+    }
+}
@@ -0,0 +1,277 @@
+use super::*;
+
+/// Builds a `Linter` with a custom set of rules.
+///
+/// ```
+/// use typographic_linter::Linter;
+///
+/// let linter = Linter::builder().locale("fr").disable("quotes").build().unwrap();
+/// ```
+pub struct LinterBuilder {
+    locale: String,
+    filters: Vec<Box<dyn LinterFilter>>,
+    only: Option<Vec<String>>,
+    disabled: Vec<String>,
+    severities: Vec<(String, Severity)>,
+    french_spacing: FrenchSpacing,
+    /// First invalid custom rule of a configuration, returned when building the linter.
+    error: Option<LinterError>,
+}
+
+impl Default for LinterBuilder {
+    fn default() -> LinterBuilder {
+        LinterBuilder {
+            locale: "en".to_string(),
+            filters: Vec::new(),
+            only: None,
+            disabled: Vec::new(),
+            severities: Vec::new(),
+            french_spacing: FrenchSpacing::default(),
+            error: None,
+        }
+    }
+}
+
+impl LinterBuilder {
+    pub fn locale(mut self, locale: &str) -> LinterBuilder {
+        self.locale = locale.to_string();
+        self
+    }
+
+    /// Registers a custom filter, in addition to the built-in ones.
+    pub fn filter(mut self, filter: Box<dyn LinterFilter>) -> LinterBuilder {
+        self.filters.push(filter);
+        self
+    }
+
+    /// Turns off the rule with the given identifier.
+    pub fn disable(mut self, rule: &str) -> LinterBuilder {
+        if !self.disabled.iter().any(|disabled| disabled == rule) {
+            self.disabled.push(rule.to_string());
+        }
+
+        self
+    }
+
+    /// Turns back on a rule that has been disabled.
+    pub fn enable(mut self, rule: &str) -> LinterBuilder {
+        self.disabled.retain(|disabled| disabled != rule);
+        self
+    }
+
+    /// Only registers the rules with the given identifiers.
+    pub fn only(mut self, rules: &[&str]) -> LinterBuilder {
+        self.only = Some(rules.iter().map(|rule| rule.to_string()).collect());
+        self
+    }
+
+    /// Overrides the default severity of a rule.
+    pub fn severity(mut self, rule: &str, severity: Severity) -> LinterBuilder {
+        self.severities.retain(|(overridden, _)| overridden != rule);
+        self.severities.push((rule.to_string(), severity));
+        self
+    }
+
+    /// Sets how strictly French non-breaking spaces are checked.
+    pub fn french_spacing(mut self, spacing: FrenchSpacing) -> LinterBuilder {
+        self.french_spacing = spacing;
+        self
+    }
+
+    /// Applies the locale, rules and severities of a configuration, regardless of overrides (see
+    /// `Config::builder_for`).
+    pub fn config(mut self, config: &Config) -> LinterBuilder {
+        if let Some(ref locale) = config.locale {
+            self = self.locale(locale);
+        }
+
+        if let Some(ref only) = config.only {
+            self.only = Some(only.clone());
+        }
+
+        if let Some(spacing) = config.french_spacing {
+            self = self.french_spacing(spacing);
+        }
+
+        for rule in &config.disable {
+            self = self.disable(rule);
+        }
+
+        for (rule, severity) in &config.severities {
+            self = self.severity(rule, *severity);
+        }
+
+        for rule in &config.rules {
+            match CustomFilter::new(rule.clone()) {
+                Ok(filter) => self = self.filter(Box::new(filter)),
+                Err(error) => {
+                    self.error.get_or_insert(error);
+                }
+            }
+        }
+
+        self
+    }
+
+    /// Builds the linter, failing on invalid locales or rules, and on unknown rule identifiers.
+    pub fn build(self) -> Result<Linter, LinterError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+
+        let locale = self.locale.parse()?;
+        let filters = Linter::default_filters(&locale, self.french_spacing)
+            .into_iter()
+            .chain(self.filters)
+            .collect::<Vec<Box<dyn LinterFilter>>>();
+
+        // A misspelled identifier would silently leave a rule on, or with its default severity.
+        let rules = self
+            .only
+            .iter()
+            .flatten()
+            .chain(&self.disabled)
+            .chain(self.severities.iter().map(|(rule, _)| rule));
+
+        for rule in rules {
+            if rule != UNUSED_SUPPRESSION_RULE && !filters.iter().any(|filter| filter.id() == rule) {
+                return Err(LinterError::UnknownRule(rule.clone()));
+            }
+        }
+
+        let mut linter = Linter {
+            filters: Vec::new(),
+            regex_set: OnceLock::new(),
+            only: self.only,
+            disabled: self.disabled,
+            severities: self.severities,
+            locale,
+        };
+
+        for filter in filters {
+            linter.register(filter)?;
+        }
+
+        Ok(linter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(linter: &Linter) -> Vec<&str> {
+        linter.filters.iter().map(|compiled| compiled.filter.id()).collect()
+    }
+
+    #[test]
+    fn test_builder_with_default_rules() {
+        let linter = Linter::builder().locale("fr").build().unwrap();
+
+        assert_eq!(
+            vec![
+                "curly-apostrophe",
+                "dash",
+                "ellipsis-symbol",
+                "no-space-before-comma",
+                "number-format",
+                "price",
+                "quotes",
+                "space-before-double-punctuation",
+                "unit",
+            ],
+            rules(&linter)
+        );
+    }
+
+    #[test]
+    fn test_builder_with_disabled_rules() {
+        let linter = Linter::builder()
+            .locale("fr")
+            .disable("quotes")
+            .disable("price")
+            .enable("price")
+            .build()
+            .unwrap();
+
+        assert!(linter.check("\"Oui\"").is_empty());
+        assert_eq!(
+            vec![
+                "curly-apostrophe",
+                "dash",
+                "ellipsis-symbol",
+                "no-space-before-comma",
+                "number-format",
+                "price",
+                "space-before-double-punctuation",
+                "unit",
+            ],
+            rules(&linter)
+        );
+    }
+
+    #[test]
+    fn test_builder_with_only_some_rules() {
+        let linter = Linter::builder()
+            .only(&["curly-apostrophe", "quotes"])
+            .disable("quotes")
+            .build()
+            .unwrap();
+
+        assert_eq!(vec!["curly-apostrophe"], rules(&linter));
+    }
+
+    #[test]
+    fn test_builder_with_severities() {
+        let linter = Linter::builder()
+            .severity("curly-apostrophe", Severity::Info)
+            .severity("curly-apostrophe", Severity::Error)
+            .build()
+            .unwrap();
+
+        let warnings = linter.check("It's me...").warnings;
+
+        assert_eq!(Severity::Error, warnings[0].severity);
+        assert_eq!(Severity::Info, warnings[1].severity);
+    }
+
+    #[test]
+    fn test_builder_with_french_spacing() {
+        let linter = Linter::builder().locale("fr").french_spacing(FrenchSpacing::Strict).build().unwrap();
+
+        assert_eq!("«\u{a0}Oui\u{202f}!\u{a0}»", linter.fix("\"Oui!\""));
+    }
+
+    struct InvalidFilter {}
+
+    impl LinterFilter for InvalidFilter {
+        fn id(&self) -> &str {
+            "invalid"
+        }
+
+        fn message(&self) -> &str {
+            ""
+        }
+
+        fn regex_pattern(&self) -> String {
+            "(".to_string()
+        }
+    }
+
+    #[test]
+    fn test_builder_with_an_invalid_filter() {
+        let result = Linter::builder().filter(Box::new(InvalidFilter {})).build();
+
+        assert!(matches!(result, Err(LinterError::InvalidRegex { ref rule, .. }) if rule == "invalid"));
+        assert!(Linter::builder().filter(Box::new(InvalidFilter {})).disable("invalid").build().is_ok());
+    }
+
+    #[test]
+    fn test_builder_with_unknown_rules() {
+        let result = Linter::builder().disable("quote").build();
+
+        assert!(matches!(result, Err(LinterError::UnknownRule(ref rule)) if rule == "quote"));
+        assert!(Linter::builder().only(&["curly-apostrophe", "elipsis"]).build().is_err());
+        assert!(Linter::builder().severity("unused-suppression", Severity::Error).build().is_ok());
+    }
+}
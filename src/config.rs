@@ -0,0 +1,386 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use glob::{MatchOptions, Pattern};
+
+use super::*;
+use errors::{ConfigError, LinterError};
+
+pub const CONFIG_FILE_NAME: &str = "typographic-linter.toml";
+
+/// Project configuration, usually loaded from a `typographic-linter.toml` file:
+///
+/// ```toml
+/// locale = "fr"
+/// french_spacing = "strict"
+/// disable = ["quotes"]
+/// ignore = ["vendor/**"]
+///
+/// [severities]
+/// ellipsis-symbol = "error"
+///
+/// [[overrides]]
+/// paths = ["docs/en/**"]
+/// locale = "en"
+///
+/// [[rules]]
+/// id = "product-name"
+/// pattern = "\\bGithub\\b"
+/// message = "Please write “GitHub” with a capital H."
+/// replacement = "GitHub"
+/// ```
+///
+/// Custom rules (see `CustomRule`) can also be shared in TOML or JSON files listed in
+/// `rule_files`. Globs and paths are relative to the directory of the configuration file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub locale: Option<String>,
+    /// How strictly French non-breaking spaces are checked: `strict`, `lenient` or `web-safe`.
+    pub french_spacing: Option<FrenchSpacing>,
+    /// When set, only these rules are registered.
+    pub only: Option<Vec<String>>,
+    pub disable: Vec<String>,
+    pub severities: BTreeMap<String, Severity>,
+    pub ignore: Vec<String>,
+    pub overrides: Vec<ConfigOverride>,
+    pub rules: Vec<CustomRule>,
+    pub rule_files: Vec<PathBuf>,
+    #[serde(skip)]
+    pub root: PathBuf,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleFile {
+    rules: Vec<CustomRule>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConfigOverride {
+    pub paths: Vec<String>,
+    pub locale: Option<String>,
+}
+
+impl Config {
+    /// Looks for a configuration file in the directory of `path` and its ancestors, and loads it.
+    pub fn discover(path: &Path) -> Result<Option<Config>, LinterError> {
+        match Config::find(path).map_err(ConfigError::Io)? {
+            Some(config_path) => Config::load(&config_path).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the path of the configuration file applying to `path`, if any.
+    pub fn find(path: &Path) -> io::Result<Option<PathBuf>> {
+        let path = fs::canonicalize(path)?;
+
+        let directory = if path.is_dir() { path.as_path() } else { path.parent().unwrap_or(&path) };
+
+        Ok(directory
+            .ancestors()
+            .map(|ancestor| ancestor.join(CONFIG_FILE_NAME))
+            .find(|candidate| candidate.is_file()))
+    }
+
+    pub fn load(path: &Path) -> Result<Config, LinterError> {
+        let content = fs::read_to_string(path).map_err(ConfigError::Io)?;
+
+        let path = fs::canonicalize(path).map_err(ConfigError::Io)?;
+
+        Config::parse_in(&content, path.parent().unwrap_or(&path))
+    }
+
+    /// Parses a configuration, relative to the current directory.
+    pub fn parse(content: &str) -> Result<Config, LinterError> {
+        let root = std::env::current_dir()
+            .and_then(fs::canonicalize)
+            .unwrap_or_default();
+
+        Config::parse_in(content, &root)
+    }
+
+    /// Loads the custom rules of a TOML or JSON rule file (depending on its extension).
+    pub fn load_rules(path: &Path) -> Result<Vec<CustomRule>, LinterError> {
+        let content = fs::read_to_string(path).map_err(ConfigError::Io)?;
+
+        let rule_file: RuleFile = if path.extension().is_some_and(|extension| extension == "json") {
+            serde_json::from_str(&content).map_err(ConfigError::ParseJson)?
+        } else {
+            toml::from_str(&content).map_err(ConfigError::Parse)?
+        };
+
+        validate_rules(&rule_file.rules)?;
+
+        Ok(rule_file.rules)
+    }
+
+    fn parse_in(content: &str, root: &Path) -> Result<Config, LinterError> {
+        let mut config: Config = toml::from_str(content).map_err(ConfigError::Parse)?;
+
+        let globs = config
+            .ignore
+            .iter()
+            .chain(config.overrides.iter().flat_map(|config_override| &config_override.paths));
+
+        for glob in globs {
+            Pattern::new(glob).map_err(ConfigError::InvalidGlob)?;
+        }
+
+        let locales = config
+            .locale
+            .iter()
+            .chain(config.overrides.iter().flat_map(|config_override| &config_override.locale));
+
+        for locale in locales {
+            locale.parse::<Locale>()?;
+        }
+
+        validate_rules(&config.rules)?;
+
+        for rule_file in &config.rule_files {
+            let mut rules = Config::load_rules(&root.join(rule_file))?;
+
+            config.rules.append(&mut rules);
+        }
+
+        config.root = root.to_path_buf();
+
+        Ok(config)
+    }
+
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        matches_any(&self.ignore, &self.relative_path(path))
+    }
+
+    /// Returns the locale of the given path, taking the last matching override into account.
+    pub fn locale_for(&self, path: &Path) -> Option<&str> {
+        let path = self.relative_path(path);
+
+        self.overrides
+            .iter()
+            .rev()
+            .filter(|config_override| matches_any(&config_override.paths, &path))
+            .find_map(|config_override| config_override.locale.as_deref())
+            .or(self.locale.as_deref())
+    }
+
+    /// Returns a builder configured for the given path.
+    pub fn builder_for(&self, path: &Path) -> LinterBuilder {
+        let builder = Linter::builder().config(self);
+
+        match self.locale_for(path) {
+            Some(locale) => builder.locale(locale),
+            None => builder,
+        }
+    }
+
+    fn relative_path(&self, path: &Path) -> PathBuf {
+        let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+
+        match path.strip_prefix(&self.root) {
+            Ok(relative_path) => relative_path.to_path_buf(),
+            Err(_) => path,
+        }
+    }
+}
+
+fn validate_rules(rules: &[CustomRule]) -> Result<(), LinterError> {
+    for rule in rules {
+        CustomFilter::new(rule.clone())?;
+    }
+
+    Ok(())
+}
+
+/// Returns whether a path matches one of the globs, `*` not matching `/` as in `.gitignore` files.
+fn matches_any(globs: &[String], path: &Path) -> bool {
+    let options = MatchOptions { require_literal_separator: true, ..Default::default() };
+
+    globs
+        .iter()
+        .filter_map(|glob| Pattern::new(glob).ok())
+        .any(|pattern| pattern.matches_path_with(path, options))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns an empty directory of its own for a test, as tests run in parallel.
+    fn temp_dir(test: &str) -> PathBuf {
+        let directory = std::env::temp_dir().join(format!("typographic-linter-{}-{}", test, std::process::id()));
+
+        let _ = fs::remove_dir_all(&directory);
+        fs::create_dir_all(&directory).unwrap();
+
+        directory
+    }
+
+    fn config() -> Config {
+        Config::parse(
+            r#"
+            locale = "fr"
+            disable = ["quotes"]
+            ignore = ["vendor/**", "CHANGELOG.md", "notes/*.md"]
+
+            [severities]
+            ellipsis-symbol = "error"
+
+            [[overrides]]
+            paths = ["docs/en/**"]
+            locale = "en"
+            "#,
+        ).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let config = config();
+
+        assert_eq!(Some("fr".to_string()), config.locale);
+        assert_eq!(None, config.only);
+        assert_eq!(vec!["quotes".to_string()], config.disable);
+        assert_eq!(Some(&Severity::Error), config.severities.get("ellipsis-symbol"));
+    }
+
+    #[test]
+    fn test_parse_with_errors() {
+        assert!(matches!(Config::parse("locale = 12"), Err(LinterError::Config(ConfigError::Parse(_)))));
+        assert!(matches!(Config::parse("unknown = true"), Err(LinterError::Config(ConfigError::Parse(_)))));
+        assert!(matches!(Config::parse("ignore = [\"[\"]"), Err(LinterError::Config(ConfigError::InvalidGlob(_)))));
+        assert!(matches!(Config::parse("locale = \"pt\""), Err(LinterError::UnsupportedLocale(_))));
+        assert!(matches!(Config::parse("french_spacing = \"loose\""), Err(LinterError::Config(ConfigError::Parse(_)))));
+    }
+
+    #[test]
+    fn test_parse_with_french_spacing() {
+        let config = Config::parse("locale = \"fr\"\nfrench_spacing = \"web-safe\"").unwrap();
+
+        assert_eq!(Some(FrenchSpacing::WebSafe), config.french_spacing);
+
+        let linter = config.builder_for(Path::new("README.md")).build().unwrap();
+
+        assert_eq!("Oui\u{a0}!", linter.fix("Oui\u{202f}!"));
+    }
+
+    #[test]
+    fn test_parse_with_custom_rules() {
+        let config = Config::parse(
+            r#"
+            [[rules]]
+            id = "product-name"
+            pattern = "\\bGithub\\b"
+            message = "Please write “GitHub” with a capital H."
+            replacement = "GitHub"
+            "#,
+        ).unwrap();
+
+        let linter = config.builder_for(Path::new("README.md")).build().unwrap();
+
+        assert_eq!("It’s on GitHub.", linter.fix("It’s on Github."));
+
+        let result = Config::parse("[[rules]]\nid = \"invalid\"\npattern = \"(\"\nmessage = \"Invalid.\"");
+
+        assert!(matches!(result, Err(LinterError::InvalidRegex { .. })));
+
+        let result = Config::parse("[[rules]]\nid = \"ok\"\npattern = \"Ok\"\nmessage = \"OK.\"\nreplacement = \"$1\"");
+
+        assert!(matches!(result, Err(LinterError::InvalidReplacement { ref rule, .. }) if rule == "ok"));
+    }
+
+    #[test]
+    fn test_builder_for_with_unknown_rules() {
+        let result = Config::parse("disable = [\"quote\"]").unwrap().builder_for(Path::new("README.md")).build();
+
+        assert!(matches!(result, Err(LinterError::UnknownRule(ref rule)) if rule == "quote"));
+    }
+
+    #[test]
+    fn test_builder_for_with_an_invalid_rule() {
+        let config = Config {
+            rules: vec![CustomRule {
+                id: "invalid".to_string(),
+                pattern: "(".to_string(),
+                message: "Invalid.".to_string(),
+                locales: Vec::new(),
+                replacement: None,
+                severity: Severity::Warning,
+            }],
+            ..Default::default()
+        };
+        let result = config.builder_for(Path::new("README.md")).build();
+
+        assert!(matches!(result, Err(LinterError::InvalidRegex { ref rule, .. }) if rule == "invalid"));
+    }
+
+    #[test]
+    fn test_load_rules_from_a_json_file() {
+        let directory = temp_dir("load-rules-from-a-json-file");
+        let path = directory.join("rules.json");
+
+        fs::write(
+            &path,
+            r#"{ "rules": [{ "id": "ok", "pattern": "\\bOk\\b", "message": "Please write “OK”.", "locales": ["en"] }] }"#,
+        ).unwrap();
+
+        let rules = Config::load_rules(&path).unwrap();
+
+        assert_eq!(1, rules.len());
+        assert_eq!("ok", rules[0].id);
+        assert_eq!(vec!["en".to_string()], rules[0].locales);
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_is_ignored() {
+        let config = config();
+
+        assert!(config.is_ignored(Path::new("vendor/lib/README.md")));
+        assert!(config.is_ignored(Path::new("CHANGELOG.md")));
+        assert!(!config.is_ignored(Path::new("docs/README.md")));
+        assert!(config.is_ignored(Path::new("notes/todo.md")));
+        assert!(!config.is_ignored(Path::new("notes/en/deep/todo.md")));
+    }
+
+    #[test]
+    fn test_locale_for() {
+        let config = config();
+
+        assert_eq!(Some("en"), config.locale_for(Path::new("docs/en/index.md")));
+        assert_eq!(Some("fr"), config.locale_for(Path::new("docs/fr/index.md")));
+    }
+
+    #[test]
+    fn test_builder_for() {
+        let config = config();
+
+        let linter = config.builder_for(Path::new("docs/fr/index.md")).build().unwrap();
+        let warnings = linter.check("\"Oui\"...").warnings;
+
+        assert_eq!(1, warnings.len());
+        assert_eq!("ellipsis-symbol", warnings[0].rule);
+        assert_eq!(Severity::Error, warnings[0].severity);
+    }
+
+    #[test]
+    fn test_discover() {
+        let root = temp_dir("discover");
+        let directory = root.join("docs").join("en");
+
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(root.join(CONFIG_FILE_NAME), "locale = \"fr\"\n[[overrides]]\npaths = [\"docs/en/*\"]\nlocale = \"en\"\n").unwrap();
+        fs::write(directory.join("index.md"), "").unwrap();
+
+        let config = Config::discover(&directory.join("index.md")).unwrap().unwrap();
+
+        assert_eq!(fs::canonicalize(&root).unwrap(), config.root);
+        assert_eq!(Some("en"), config.locale_for(&directory.join("index.md")));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
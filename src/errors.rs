@@ -1,30 +1,208 @@
 use std::error::Error;
 use std::fmt;
+use std::io;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LinterFix {
+    pub start: usize,
+    pub end: usize,
+    pub replacement: String,
+}
+
+/// A 1-based position in a text, with the column counted both in characters and in UTF-16 code
+/// units (as expected by LSP and JavaScript consumers).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+    pub utf16_column: usize,
+}
+
+/// Maps byte offsets of a text to positions, indexing the start of its lines once.
+pub struct LineIndex<'a> {
+    text: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    pub fn new(text: &'a str) -> LineIndex<'a> {
+        let mut line_starts = vec![0];
+
+        line_starts.extend(text.match_indices('\n').map(|(offset, _)| offset + 1));
+
+        LineIndex { text, line_starts }
+    }
+
+    pub fn position(&self, offset: usize) -> Position {
+        let offset = offset.min(self.text.len());
+
+        let line = match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(line) => line - 1,
+        };
+
+        let prefix = &self.text[self.line_starts[line]..offset];
+
+        Position {
+            line: line + 1,
+            column: prefix.chars().count() + 1,
+            utf16_column: prefix.encode_utf16().count() + 1,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    #[default]
+    Warning,
+    Info,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+        })
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
 pub struct LinterWarning {
-    pub message: &'static str,
+    /// Stable identifier of the rule that raised the warning (ex: `curly-apostrophe`).
+    pub rule: String,
+    pub severity: Severity,
+    pub message: String,
     pub start: usize,
-    pub end: usize
+    pub end: usize,
+    /// Position of `start`, filled in by the `Linter`.
+    pub start_position: Position,
+    /// Position of `end`, filled in by the `Linter`.
+    pub end_position: Position,
+    pub fixes: Vec<LinterFix>,
+    /// Key of the catalogue entry the warning was raised in (ex: the `msgid` of a PO entry).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entry: Option<String>,
+}
+
+impl LinterWarning {
+    /// Sets the positions of the warning from its byte offsets.
+    pub fn locate(&mut self, index: &LineIndex) {
+        self.start_position = index.position(self.start);
+        self.end_position = index.position(self.end);
+    }
 }
 
 impl fmt::Display for LinterWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {} [{}] ({}, {})", self.severity, self.message, self.rule, self.start, self.end)
+    }
+}
+
+/// Errors raised when setting up a `Linter`, as opposed to the warnings it reports.
+#[derive(Debug)]
+pub enum LinterError {
+    /// The locale isn’t a valid BCP 47 tag.
+    InvalidLocale(String),
+    /// The locale is valid, but its language isn’t supported.
+    UnsupportedLocale(String),
+    /// The configuration can’t be loaded.
+    Config(ConfigError),
+    /// The regular expression of a rule doesn’t compile.
+    InvalidRegex { rule: String, error: regex::Error },
+    /// The replacement of a custom rule refers to a capture group its pattern doesn’t have.
+    InvalidReplacement { rule: String, group: String },
+    /// A rule is disabled, enabled or given a severity, but no rule has this identifier.
+    UnknownRule(String),
+    /// The input format isn’t supported.
+    UnknownInputFormat(String),
+    /// The output format isn’t supported.
+    UnknownOutputFormat(String),
+}
+
+impl fmt::Display for LinterError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            LinterWarning { message, ref start, ref end } => {
-                f.write_str(
-                    format!("Warning: {} ({}, {})", message, start, end).as_str()
-                )
-            },
+            LinterError::InvalidLocale(ref locale) => write!(f, "Invalid locale: `{}`", locale),
+            LinterError::UnsupportedLocale(ref locale) => write!(f, "Unsupported locale: `{}`", locale),
+            LinterError::Config(ref error) => error.fmt(f),
+            LinterError::InvalidRegex { ref rule, ref error } => write!(f, "Invalid pattern for rule `{}`: {}", rule, error),
+            LinterError::InvalidReplacement { ref rule, ref group } => {
+                write!(f, "Invalid replacement for rule `{}`: unknown capture group `{}`", rule, group)
+            }
+            LinterError::UnknownRule(ref rule) => write!(f, "Unknown rule: `{}`", rule),
+            LinterError::UnknownInputFormat(ref format) => write!(f, "Unknown input format: `{}`", format),
+            LinterError::UnknownOutputFormat(ref format) => write!(f, "Unknown output format: `{}`", format),
         }
     }
 }
 
-impl Error for LinterWarning {
-    #[allow(unused_variables)]
-    fn description(&self) -> &str {
+impl Error for LinterError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
         match *self {
-            LinterWarning { message, ref start, ref end } => { message },
+            LinterError::InvalidLocale(_)
+            | LinterError::UnsupportedLocale(_)
+            | LinterError::InvalidReplacement { .. }
+            | LinterError::UnknownRule(_)
+            | LinterError::UnknownInputFormat(_)
+            | LinterError::UnknownOutputFormat(_) => None,
+            LinterError::Config(ref error) => Some(error),
+            LinterError::InvalidRegex { ref error, .. } => Some(error),
         }
     }
 }
+
+impl From<ConfigError> for LinterError {
+    fn from(error: ConfigError) -> LinterError {
+        LinterError::Config(error)
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(io::Error),
+    Parse(toml::de::Error),
+    ParseJson(serde_json::Error),
+    InvalidGlob(glob::PatternError),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ConfigError::Io(ref error) => write!(f, "Can’t read the configuration: {}", error),
+            ConfigError::Parse(ref error) => write!(f, "Invalid configuration: {}", error),
+            ConfigError::ParseJson(ref error) => write!(f, "Invalid configuration: {}", error),
+            ConfigError::InvalidGlob(ref error) => write!(f, "Invalid glob in the configuration: {}", error),
+        }
+    }
+}
+
+impl Error for ConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            ConfigError::Io(ref error) => Some(error),
+            ConfigError::Parse(ref error) => Some(error),
+            ConfigError::ParseJson(ref error) => Some(error),
+            ConfigError::InvalidGlob(ref error) => Some(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_index_positions() {
+        let index = LineIndex::new("Ça va ?\n“Oui” 😀 !\n");
+
+        assert_eq!(Position { line: 1, column: 1, utf16_column: 1 }, index.position(0));
+        assert_eq!(Position { line: 1, column: 7, utf16_column: 7 }, index.position(7));
+        assert_eq!(Position { line: 2, column: 1, utf16_column: 1 }, index.position(9));
+        assert_eq!(Position { line: 2, column: 9, utf16_column: 10 }, index.position(24));
+        assert_eq!(Position { line: 3, column: 1, utf16_column: 1 }, index.position(26));
+    }
+}
@@ -3,6 +3,14 @@ use super::*;
 pub struct CurlyApostropheFilter {}
 
 impl LinterFilter for CurlyApostropheFilter {
+    fn fixes(&self, _text: &str, start: usize, end: usize) -> Vec<LinterFix> {
+        vec![LinterFix { start, end, replacement: "’".to_string() }]
+    }
+
+    fn id(&self) -> &'static str {
+        "curly-apostrophe"
+    }
+
     fn message(&self) -> &'static str {
         "Please use curly apostrophes."
     }
@@ -20,11 +28,7 @@ mod tests {
     fn test_curly_apostrophe_filter_with_straight_apostrophe() {
         let filter = CurlyApostropheFilter {};
 
-        let result = filter.check("It's me, Mario!");
-
-        assert!(result.is_err());
-
-        let warnings = result.err().unwrap();
+        let warnings = filter.check("It's me, Mario!");
 
         assert_eq!(1, warnings.len());
         assert_eq!("Please use curly apostrophes.", warnings[0].message);
@@ -32,14 +36,25 @@ mod tests {
         assert_eq!(3, warnings[0].end);
     }
 
+    #[test]
+    fn test_curly_apostrophe_filter_fixes() {
+        let filter = CurlyApostropheFilter {};
+
+        let warnings = filter.check("It's me, Mario!");
+
+        assert_eq!(
+            vec![LinterFix { start: 2, end: 3, replacement: "’".to_string() }],
+            warnings[0].fixes
+        );
+    }
+
     #[test]
     fn test_curly_apostrophe_filter_with_curly_apostrophe() {
         let filter = CurlyApostropheFilter {};
 
-        let result = filter.check("It’s me, Mario!");
+        let warnings = filter.check("It’s me, Mario!");
 
-        assert_eq!(false, result.is_err());
-        assert_eq!((), result.unwrap());
+        assert!(warnings.is_empty());
     }
 }
 
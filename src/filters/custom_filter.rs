@@ -0,0 +1,221 @@
+use super::*;
+
+/// A rule declared in a configuration file rather than in Rust:
+///
+/// ```toml
+/// [[rules]]
+/// id = "product-name"
+/// pattern = "\\b[Gg]ithub\\b"
+/// message = "Please write “GitHub” with a capital H."
+/// replacement = "GitHub"
+/// ```
+///
+/// The replacement can refer to the capture groups of the pattern (`$1`, `${name}`).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomRule {
+    pub id: String,
+    pub pattern: String,
+    pub message: String,
+    #[serde(default)]
+    pub locales: Vec<String>,
+    pub replacement: Option<String>,
+    #[serde(default)]
+    pub severity: Severity,
+}
+
+pub struct CustomFilter {
+    rule: CustomRule,
+    regex: Regex,
+}
+
+impl CustomFilter {
+    pub fn new(rule: CustomRule) -> Result<CustomFilter, LinterError> {
+        let regex =
+            Regex::new(&rule.pattern).map_err(|error| LinterError::InvalidRegex { rule: rule.id.clone(), error })?;
+
+        if let Some(group) = rule.replacement.as_deref().and_then(|replacement| unknown_group(&regex, replacement)) {
+            return Err(LinterError::InvalidReplacement { rule: rule.id.clone(), group });
+        }
+
+        Ok(CustomFilter { rule, regex })
+    }
+}
+
+/// Returns the first capture group a replacement refers to (ex: `$2`, `$name` or `${name}`) that
+/// the regular expression doesn’t have, as they would silently be replaced with nothing.
+fn unknown_group(regex: &Regex, replacement: &str) -> Option<String> {
+    let mut rest = replacement;
+
+    while let Some(dollar) = rest.find('$') {
+        rest = &rest[dollar + 1..];
+
+        // `$$` stands for a dollar sign.
+        if let Some(escaped) = rest.strip_prefix('$') {
+            rest = escaped;
+            continue;
+        }
+
+        let (group, length) = match rest.strip_prefix('{') {
+            Some(braced) => match braced.find('}') {
+                Some(end) => (&braced[..end], end + 2),
+                None => continue,
+            },
+            None => {
+                let length = rest
+                    .find(|character: char| !character.is_ascii_alphanumeric() && character != '_')
+                    .unwrap_or(rest.len());
+
+                (&rest[..length], length)
+            }
+        };
+
+        rest = &rest[length..];
+
+        let is_defined = match group.parse::<usize>() {
+            Ok(index) => index < regex.captures_len(),
+            Err(_) => group.is_empty() || regex.capture_names().any(|name| name == Some(group)),
+        };
+
+        if !is_defined {
+            return Some(group.to_string());
+        }
+    }
+
+    None
+}
+
+impl LinterFilter for CustomFilter {
+    fn check(&self, text: &str) -> Vec<LinterWarning> {
+        self.check_with_regex(&self.regex, text)
+    }
+
+    fn fixes(&self, text: &str, start: usize, end: usize) -> Vec<LinterFix> {
+        let replacement = match self.rule.replacement {
+            Some(ref replacement) => replacement,
+            None => return Vec::new(),
+        };
+
+        match self.regex.captures_at(text, start) {
+            Some(captures) if captures.get(0).is_some_and(|found| found.range() == (start..end)) => {
+                let mut expanded = String::new();
+
+                captures.expand(replacement, &mut expanded);
+
+                vec![LinterFix { start, end, replacement: expanded }]
+            },
+            _ => Vec::new(),
+        }
+    }
+
+    fn id(&self) -> &str {
+        &self.rule.id
+    }
+
+    fn locales(&self) -> Vec<&str> {
+        self.rule.locales.iter().map(String::as_str).collect()
+    }
+
+    fn message(&self) -> &str {
+        &self.rule.message
+    }
+
+    fn regex_pattern(&self) -> String {
+        self.rule.pattern.clone()
+    }
+
+    fn severity(&self) -> Severity {
+        self.rule.severity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(replacement: Option<&str>) -> CustomFilter {
+        CustomFilter::new(CustomRule {
+            id: "unit-space".to_string(),
+            pattern: r"(?P<value>\d+) ?(?P<unit>km|kg)\b".to_string(),
+            message: "Please use a non-breaking space between a value and its unit.".to_string(),
+            locales: vec!["fr".to_string()],
+            replacement: replacement.map(str::to_string),
+            severity: Severity::Error,
+        }).unwrap()
+    }
+
+    #[test]
+    fn test_filter_with_a_warning() {
+        let filter = filter(None);
+
+        let warnings = filter.check("Il a couru 10km puis 5 km.");
+
+        assert_eq!(2, warnings.len());
+        assert_eq!("unit-space", warnings[0].rule);
+        assert_eq!(Severity::Error, warnings[0].severity);
+        assert_eq!("Please use a non-breaking space between a value and its unit.", warnings[0].message);
+        assert_eq!(11, warnings[0].start);
+        assert_eq!(15, warnings[0].end);
+        assert!(warnings[0].fixes.is_empty());
+    }
+
+    #[test]
+    fn test_filter_fixes_with_capture_groups() {
+        let filter = filter(Some("${value}\u{a0}$unit"));
+
+        let warnings = filter.check("Il a couru 10km puis 5 km.");
+
+        assert_eq!(
+            vec![LinterFix { start: 11, end: 15, replacement: "10\u{a0}km".to_string() }],
+            warnings[0].fixes
+        );
+        assert_eq!(
+            vec![LinterFix { start: 21, end: 25, replacement: "5\u{a0}km".to_string() }],
+            warnings[1].fixes
+        );
+    }
+
+    #[test]
+    fn test_filter_with_no_warnings() {
+        let filter = filter(None);
+
+        assert!(filter.check("Il a couru 10\u{a0}km.").is_empty());
+        assert_eq!(vec!["fr"], filter.locales());
+    }
+
+    #[test]
+    fn test_filter_with_an_invalid_pattern() {
+        let rule = CustomRule {
+            id: "invalid".to_string(),
+            pattern: "(".to_string(),
+            message: "Invalid.".to_string(),
+            locales: Vec::new(),
+            replacement: None,
+            severity: Severity::Warning,
+        };
+
+        assert!(CustomFilter::new(rule).is_err());
+    }
+
+    #[test]
+    fn test_filter_with_an_invalid_replacement() {
+        let rule = |replacement: &str| CustomRule {
+            id: "unit-space".to_string(),
+            pattern: r"(?P<value>\d+) ?(km)".to_string(),
+            message: "Invalid.".to_string(),
+            locales: Vec::new(),
+            replacement: Some(replacement.to_string()),
+            severity: Severity::Warning,
+        };
+
+        assert!(CustomFilter::new(rule("$value\u{a0}$2, $$3 ${value}")).is_ok());
+        assert!(matches!(
+            CustomFilter::new(rule("${value}\u{a0}$unit")),
+            Err(LinterError::InvalidReplacement { ref group, .. }) if group == "unit"
+        ));
+        assert!(matches!(
+            CustomFilter::new(rule("$1\u{a0}$3")),
+            Err(LinterError::InvalidReplacement { ref group, .. }) if group == "3"
+        ));
+    }
+}
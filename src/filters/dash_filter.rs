@@ -0,0 +1,275 @@
+use super::*;
+
+/// Spaces that can surround a dash.
+const SPACES: &str = r"[ \u{a0}\u{202f}]";
+
+/// How a locale writes the dashes setting off a break in a sentence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DashStyle {
+    /// An em dash without spaces (ex: `it was—as usual—late`).
+    UnspacedEm,
+    /// An en dash between spaces (ex: `it was – as usual – late`).
+    SpacedEn,
+    /// An en or em dash between spaces, the one inside the break being non-breaking
+    /// (ex: `il était –\u{a0}comme d’habitude\u{a0}– en retard`).
+    NonBreakingSpaced,
+    /// An em dash, spaced as the author likes (ex: `llegó —como siempre— tarde`).
+    Em,
+}
+
+pub struct DashFilter {
+    pub locale: Locale,
+}
+
+/// Returns whether two numbers joined by a hyphen are the bounds of a range: numbers of the same
+/// length (ex: `10-20`), short ones (ex: `9-12`) or years (ex: `1990-95`), the end being greater.
+fn is_range(from: &str, to: &str) -> bool {
+    let is_comparable = from.len() == to.len() || (from.len() <= 2 && to.len() <= 2);
+    let is_year_pair = from.len() == 4 && to.len() == 2;
+
+    match (from.parse::<u64>(), to.parse::<u64>()) {
+        (Ok(from), Ok(to)) if is_comparable => from < to,
+        (Ok(from), Ok(to)) if is_year_pair => from % 100 < to,
+        _ => false,
+    }
+}
+
+impl DashFilter {
+    fn style(&self) -> DashStyle {
+        match self.locale.language() {
+            Language::En => match self.locale.region() {
+                Some("AU" | "GB" | "IE" | "NZ") => DashStyle::SpacedEn,
+                _ => DashStyle::UnspacedEm,
+            },
+            Language::De | Language::It => DashStyle::SpacedEn,
+            Language::Es => DashStyle::Em,
+            Language::Fr => DashStyle::NonBreakingSpaced,
+        }
+    }
+
+    /// Returns the replacement of a break dash and its spaces, or `None` if they are fine.
+    /// `is_closing` tells whether the dash ends a break opened earlier in the sentence.
+    fn break_replacement(&self, before: &str, dash: &str, after: &str, is_closing: bool) -> Option<String> {
+        let is_hyphen = dash.starts_with('-');
+
+        let replacement = match self.style() {
+            DashStyle::UnspacedEm => "—".to_string(),
+            DashStyle::SpacedEn => " – ".to_string(),
+            DashStyle::NonBreakingSpaced => {
+                let dash = if dash == "—" { "—" } else { "–" };
+
+                if is_closing {
+                    format!("\u{a0}{} ", dash)
+                } else {
+                    format!(" {}\u{a0}", dash)
+                }
+            }
+            DashStyle::Em => {
+                if !is_hyphen {
+                    return None;
+                }
+
+                format!("{}—{}", before, after)
+            }
+        };
+
+        let is_fine = match self.style() {
+            DashStyle::UnspacedEm => dash == "—" && before.is_empty() && after.is_empty(),
+            DashStyle::SpacedEn => dash == "–" && !before.is_empty() && !after.is_empty(),
+            DashStyle::NonBreakingSpaced => {
+                let (inner, outer) = if is_closing { (before, after) } else { (after, before) };
+
+                !is_hyphen && (inner == "\u{a0}" || inner == "\u{202f}") && !outer.is_empty()
+            }
+            DashStyle::Em => false,
+        };
+
+        if is_fine {
+            None
+        } else {
+            Some(replacement)
+        }
+    }
+}
+
+impl LinterFilter for DashFilter {
+    fn check_with_regex(&self, regex: &Regex, text: &str) -> Vec<LinterWarning> {
+        let mut warnings = Vec::new();
+        // Number of breaks already opened or closed in the current sentence, and where it ends.
+        let mut breaks = 0;
+        let mut sentence_end = 0;
+
+        for captures in regex.captures_iter(text) {
+            if let Some(range) = captures.name("range") {
+                // Dates, phone numbers or references (ex: `2024-01-15`, `555-1234`, `+1-555`) aren’t
+                // ranges.
+                let bounds = range.as_str().split('-').collect::<Vec<&str>>();
+                let is_phone = text[..range.start()].ends_with('+');
+                let is_range = match bounds[..] {
+                    [from, to] => !is_phone && is_range(from, to),
+                    _ => false,
+                };
+
+                if is_range {
+                    let hyphen = range.start() + bounds[0].len();
+
+                    warnings.push(LinterWarning {
+                        rule: self.id().to_string(),
+                        severity: self.severity(),
+                        message: "Please use an en dash (`–`) in numeric ranges.".to_string(),
+                        start: range.start(),
+                        end: range.end(),
+                        fixes: vec![LinterFix { start: hyphen, end: hyphen + 1, replacement: "–".to_string() }],
+                        ..Default::default()
+                    });
+                }
+
+                continue;
+            }
+
+            let left = captures.name("left").unwrap();
+            let right = captures.name("right").unwrap();
+            let (before, dash, after) = (&captures["before"], &captures["dash"], &captures["after"]);
+
+            // Hyphens and en dashes joining words or numbers (ex: `dit-on`, `London–Paris`) don’t
+            // set off a break.
+            let is_break = match dash {
+                "-" => !before.is_empty() && !after.is_empty(),
+                "–" => !before.is_empty() || !after.is_empty(),
+                _ => true,
+            };
+            let is_between_numbers = left.as_str().chars().all(char::is_numeric)
+                && right.as_str().chars().all(char::is_numeric);
+
+            if !is_break || is_between_numbers {
+                continue;
+            }
+
+            if text[sentence_end..left.end()].contains(['.', '!', '?', '\n']) {
+                breaks = 0;
+            }
+
+            sentence_end = right.start();
+
+            let is_closing = breaks % 2 == 1;
+
+            breaks += 1;
+
+            if let Some(replacement) = self.break_replacement(before, dash, after, is_closing) {
+                warnings.push(LinterWarning {
+                    rule: self.id().to_string(),
+                    severity: self.severity(),
+                    message: self.message().to_string(),
+                    start: left.end(),
+                    end: right.start(),
+                    fixes: vec![LinterFix { start: left.end(), end: right.start(), replacement }],
+                    ..Default::default()
+                });
+            }
+        }
+
+        warnings
+    }
+
+    fn id(&self) -> &'static str {
+        "dash"
+    }
+
+    fn message(&self) -> &'static str {
+        match self.style() {
+            DashStyle::UnspacedEm => "Please use an em dash (`—`) without spaces to set off a break.",
+            DashStyle::SpacedEn => "Please use an en dash (`–`) between spaces to set off a break.",
+            DashStyle::NonBreakingSpaced => {
+                "Please use a dash (`–` or `—`) between spaces to set off a break, the space inside the break being non-breaking."
+            }
+            DashStyle::Em => "Please use an em dash (`—`) instead of hyphens to set off a break.",
+        }
+    }
+
+    fn regex_pattern(&self) -> String {
+        format!(
+            r"(?P<range>\b\d+(?:-\d+)+\b)|(?P<left>\w)(?P<before>{spaces}*)(?P<dash>---?|[-–—])(?P<after>{spaces}*)(?P<right>\w)",
+            spaces = SPACES
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(locale: &str) -> DashFilter {
+        DashFilter { locale: locale.parse().unwrap() }
+    }
+
+    fn fix(filter: &DashFilter, text: &str) -> String {
+        fixes::apply_fixes(text, &filter.check(text)).text
+    }
+
+    #[test]
+    fn test_dash_filter_with_ranges() {
+        let filter = filter("en");
+
+        let warnings = filter.check("See pages 10-20, not 2024-01-15, 20-10 or COVID-19.");
+
+        assert_eq!(1, warnings.len());
+        assert_eq!("Please use an en dash (`–`) in numeric ranges.", warnings[0].message);
+        assert_eq!((10, 15), (warnings[0].start, warnings[0].end));
+        assert_eq!(vec![LinterFix { start: 12, end: 13, replacement: "–".to_string() }], warnings[0].fixes);
+    }
+
+    #[test]
+    fn test_dash_filter_with_phone_numbers() {
+        let filter = filter("en");
+
+        assert!(filter.check("Call 555-1234, +1-555 or 555-123-4567.").is_empty());
+        assert_eq!("Pages 9–12, in 1990–95.", fix(&filter, "Pages 9-12, in 1990-95."));
+    }
+
+    #[test]
+    fn test_dash_filter_with_american_english() {
+        let filter = filter("en-US");
+
+        let warnings = filter.check("It was -- as usual - late. A well-known one—yes. Pages 10–20, London–Paris.");
+
+        assert_eq!(2, warnings.len());
+        assert_eq!("Please use an em dash (`—`) without spaces to set off a break.", warnings[0].message);
+        assert_eq!((6, 10), (warnings[0].start, warnings[0].end));
+        assert_eq!("It was—as usual—late.", fix(&filter, "It was -- as usual - late."));
+        assert_eq!("It was—as usual—late.", fix(&filter, "It was – as usual — late."));
+    }
+
+    #[test]
+    fn test_dash_filter_with_british_english_and_german() {
+        for locale in ["en-GB", "de"] {
+            let filter = filter(locale);
+
+            assert_eq!("It was – as usual – late.", fix(&filter, "It was--as usual - late."));
+            assert_eq!("It was – as usual – late.", fix(&filter, "It was—as usual — late."));
+            assert!(filter.check("It was – as usual\u{a0}– late, from 10–20.").is_empty());
+        }
+    }
+
+    #[test]
+    fn test_dash_filter_with_french() {
+        let filter = filter("fr");
+
+        assert_eq!(
+            "Il était –\u{a0}comme d’habitude\u{a0}– en retard.",
+            fix(&filter, "Il était - comme d’habitude - en retard.")
+        );
+        assert_eq!(
+            "Il était —\u{a0}dit-on\u{a0}— parti. Enfin –\u{a0}presque.",
+            fix(&filter, "Il était — dit-on — parti. Enfin -- presque.")
+        );
+        assert!(filter.check("Il était —\u{a0}dit-on\u{a0}— parti. Enfin –\u{a0}presque.").is_empty());
+    }
+
+    #[test]
+    fn test_dash_filter_with_spanish() {
+        let filter = filter("es");
+
+        assert_eq!("Llegó —como siempre— tarde.", fix(&filter, "Llegó --como siempre-- tarde."));
+        assert!(filter.check("Llegó —como siempre— tarde, con el anti-héroe.").is_empty());
+    }
+}
@@ -1,14 +1,110 @@
 use super::*;
 
-pub struct EllipsisSymbolFilter {}
+/// Three dots or more, possibly spaced (ex: `...`, `....`, `. . .`).
+const DOTS: &str = r"\.(?:[ \u{a0}]?\.){2,}";
+
+pub struct EllipsisSymbolFilter {
+    pub locale: Locale,
+}
+
+impl EllipsisSymbolFilter {
+    /// Returns whether the locale writes the ellipsis right after the preceding word, followed by a
+    /// space (ex: `Il était… une fois`).
+    fn is_attached(&self) -> bool {
+        matches!(self.locale.language(), Language::Es | Language::Fr | Language::It)
+    }
+
+    /// Returns the message and fixes of a match of the regular expression.
+    fn advice(&self, captures: &regex::Captures) -> (String, Vec<LinterFix>) {
+        let whole = captures.get(0).unwrap();
+
+        if let Some(dots) = captures.name("dots") {
+            // Spaces before the dots are dropped along with them in the locales attaching the
+            // ellipsis to the preceding word.
+            let start = captures.name("word").map_or(dots.start(), |word| word.end());
+            let message = if dots.as_str() == "..." {
+                self.message().to_string()
+            } else {
+                format!("Please use the ellipsis symbol (`…`) instead of dots (`{}`).", dots.as_str())
+            };
+
+            (message, vec![LinterFix { start, end: dots.end(), replacement: "…".to_string() }])
+        } else if captures.name("omission").is_some() {
+            (
+                "Please mark omissions with an ellipsis in square brackets (`[…]`).".to_string(),
+                vec![LinterFix { start: whole.start(), end: whole.end(), replacement: "[…]".to_string() }],
+            )
+        } else if let Some(spaces) = captures.name("space_before") {
+            (
+                "Please write the ellipsis right after the preceding word, without space.".to_string(),
+                vec![LinterFix { start: spaces.start(), end: spaces.end(), replacement: String::new() }],
+            )
+        } else if let Some(ellipsis) = captures.name("space_after") {
+            (
+                "Please add a space after the ellipsis.".to_string(),
+                vec![LinterFix { start: ellipsis.end(), end: ellipsis.end(), replacement: " ".to_string() }],
+            )
+        } else {
+            let spaces = captures.name("inverted").or_else(|| captures.name("closing")).unwrap();
+
+            (
+                "Please write the ellipsis right after `¡` or `¿`, and right before `!` or `?`, without space.".to_string(),
+                vec![LinterFix { start: spaces.start(), end: spaces.end(), replacement: String::new() }],
+            )
+        }
+    }
+}
 
 impl LinterFilter for EllipsisSymbolFilter {
+    fn check_with_regex(&self, regex: &Regex, text: &str) -> Vec<LinterWarning> {
+        regex
+            .captures_iter(text)
+            .map(|captures| {
+                let whole = captures.get(0).unwrap();
+                let (message, fixes) = self.advice(&captures);
+
+                LinterWarning {
+                    rule: self.id().to_string(),
+                    severity: self.severity(),
+                    message,
+                    start: whole.start(),
+                    end: whole.end(),
+                    fixes,
+                    ..Default::default()
+                }
+            })
+            .collect()
+    }
+
+    fn id(&self) -> &'static str {
+        "ellipsis-symbol"
+    }
+
     fn message(&self) -> &'static str {
         "Please use the ellipsis symbol (`…`) instead of three dots (`...`)."
     }
 
     fn regex_pattern(&self) -> String {
-        r"[.]{3}".to_string()
+        // Omissions are matched first, for their dots not to be matched on their own.
+        let mut patterns = vec![format!(r"(?P<omission>\((?:…|{dots})\)|\[{dots}\])", dots = DOTS)];
+
+        if self.is_attached() {
+            patterns.push(format!(r"(?:(?P<word>\w)[ \u{{a0}}\u{{202f}}]+)?(?P<dots>{})", DOTS));
+            patterns.push(r"\w(?P<space_before>[ \u{a0}\u{202f}]+)…".to_string());
+            patterns.push(r"\w(?P<space_after>…)\w".to_string());
+        } else {
+            patterns.push(format!("(?P<dots>{})", DOTS));
+        }
+
+        if self.locale.language() == Language::Es {
+            patterns.push(r"[¡¿](?P<inverted>[ \u{a0}]+)…|…(?P<closing>[ \u{a0}]+)[!?]".to_string());
+        }
+
+        patterns.join("|")
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Info
     }
 }
 
@@ -16,15 +112,15 @@ impl LinterFilter for EllipsisSymbolFilter {
 mod tests {
     use super::*;
 
+    fn filter(locale: &str) -> EllipsisSymbolFilter {
+        EllipsisSymbolFilter { locale: locale.parse().unwrap() }
+    }
+
     #[test]
     fn test_ellipsis_symbol_filter_with_three_dots() {
-        let filter = EllipsisSymbolFilter {};
-
-        let result = filter.check("You know...");
+        let filter = filter("en");
 
-        assert!(result.is_err());
-
-        let warnings = result.err().unwrap();
+        let warnings = filter.check("You know...");
 
         assert_eq!(1, warnings.len());
         assert_eq!("Please use the ellipsis symbol (`…`) instead of three dots (`...`).", warnings[0].message);
@@ -32,14 +128,78 @@ mod tests {
         assert_eq!(11, warnings[0].end);
     }
 
+    #[test]
+    fn test_ellipsis_symbol_filter_fixes() {
+        let filter = filter("en");
+
+        let warnings = filter.check("You know...");
+
+        assert_eq!(
+            vec![LinterFix { start: 8, end: 11, replacement: "…".to_string() }],
+            warnings[0].fixes
+        );
+    }
+
     #[test]
     fn test_ellipsis_symbol_filter_with_success() {
-        let filter = EllipsisSymbolFilter {};
+        let filter = filter("en");
 
-        let result = filter.check("You know…");
+        let warnings = filter.check("You know…");
 
-        assert_eq!(false, result.is_err());
-        assert_eq!((), result.unwrap());
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_ellipsis_symbol_filter_with_dot_variants() {
+        let filter = filter("en");
+
+        let warnings = filter.check("Wait.... Then . . . and .. . or.. done.");
+
+        assert_eq!(3, warnings.len());
+        assert_eq!("Please use the ellipsis symbol (`…`) instead of dots (`....`).", warnings[0].message);
+        assert_eq!((4, 8), (warnings[0].start, warnings[0].end));
+        assert_eq!((14, 19), (warnings[1].start, warnings[1].end));
+        assert_eq!((24, 28), (warnings[2].start, warnings[2].end));
+    }
+
+    #[test]
+    fn test_ellipsis_symbol_filter_with_omissions() {
+        let filter = filter("en");
+
+        let warnings = filter.check("He said (...) and [...] then (…), not […].");
+
+        assert_eq!(3, warnings.len());
+        assert_eq!("Please mark omissions with an ellipsis in square brackets (`[…]`).", warnings[0].message);
+        assert_eq!(vec![LinterFix { start: 8, end: 13, replacement: "[…]".to_string() }], warnings[0].fixes);
+        assert_eq!(vec![LinterFix { start: 18, end: 23, replacement: "[…]".to_string() }], warnings[1].fixes);
+    }
+
+    #[test]
+    fn test_ellipsis_symbol_filter_with_french_spacing() {
+        let filter = filter("fr");
+
+        let warnings = filter.check("Il était ... une fois… Puis …et enfin…fin. Il dit : « […] » ou « …et ».");
+
+        assert_eq!(3, warnings.len());
+        assert_eq!(vec![LinterFix { start: 9, end: 13, replacement: "…".to_string() }], warnings[0].fixes);
+        assert_eq!("Please write the ellipsis right after the preceding word, without space.", warnings[1].message);
+        assert_eq!(vec![LinterFix { start: 30, end: 31, replacement: String::new() }], warnings[1].fixes);
+        assert_eq!("Please add a space after the ellipsis.", warnings[2].message);
+        assert_eq!(vec![LinterFix { start: 45, end: 45, replacement: " ".to_string() }], warnings[2].fixes);
+
+        assert!(filter.check("Il était… une fois. « […] » « …et »").is_empty());
     }
-}
 
+    #[test]
+    fn test_ellipsis_symbol_filter_with_spanish_inverted_marks() {
+        let filter = filter("es");
+
+        let warnings = filter.check("¡ …Ya basta! ¿Y si… ?");
+
+        assert_eq!(2, warnings.len());
+        assert_eq!(vec![LinterFix { start: 2, end: 3, replacement: String::new() }], warnings[0].fixes);
+        assert_eq!(vec![LinterFix { start: 25, end: 26, replacement: String::new() }], warnings[1].fixes);
+
+        assert!(filter.check("¡…Ya basta! ¿Y si…?").is_empty());
+    }
+}
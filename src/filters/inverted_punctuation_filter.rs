@@ -0,0 +1,319 @@
+use super::*;
+
+/// Abbreviations ending with a dot within a clause (ex: `Sr. López`), besides initials.
+const ABBREVIATIONS: &[&str] = &[
+    "av", "avda", "dña", "dr", "dra", "etc", "lic", "núm", "pág", "prof", "sr", "sra", "srta", "ud", "uds", "vd", "vds",
+];
+
+/// A quotation, or the text around quotations, where questions and exclamations are matched.
+struct Level {
+    /// Opening quotation mark, if any.
+    quote: Option<char>,
+    /// Offsets and characters of the `¿` and `¡` not closed yet.
+    openers: Vec<(usize, char)>,
+    /// Offset of the first word of the current clause.
+    clause_start: Option<usize>,
+}
+
+impl Level {
+    fn new(quote: Option<char>) -> Level {
+        Level { quote, openers: Vec::new(), clause_start: None }
+    }
+}
+
+pub struct InvertedPunctuationFilter {}
+
+impl InvertedPunctuationFilter {
+    fn warning(&self, message: &str, start: usize, end: usize, fixes: Vec<LinterFix>) -> LinterWarning {
+        LinterWarning {
+            rule: self.id().to_string(),
+            severity: self.severity(),
+            message: message.to_string(),
+            start,
+            end,
+            fixes,
+            ..Default::default()
+        }
+    }
+
+    /// Warns about the questions and exclamations of `level` left open at `end`.
+    fn close(&self, level: Level, text: &str, end: usize, warnings: &mut Vec<LinterWarning>) {
+        let end = text[..end].trim_end().len();
+
+        for (start, opener) in level.openers {
+            let message = if opener == '¿' {
+                "Please end the question opened with `¿` with `?`."
+            } else {
+                "Please end the exclamation opened with `¡` with `!`."
+            };
+
+            warnings.push(self.warning(message, start, end.max(start + opener.len_utf8()), Vec::new()));
+        }
+    }
+}
+
+impl LinterFilter for InvertedPunctuationFilter {
+    fn check_with_regex(&self, _regex: &Regex, text: &str) -> Vec<LinterWarning> {
+        let mut warnings = Vec::new();
+        let mut levels = vec![Level::new(None)];
+        let mut characters = text.char_indices().peekable();
+
+        while let Some((offset, character)) = characters.next() {
+            let next = characters.peek().map(|(_, next)| *next);
+
+            match character {
+                '¿' | '¡' => {
+                    let level = levels.last_mut().unwrap();
+
+                    level.openers.push((offset, character));
+                    level.clause_start.get_or_insert(offset);
+
+                    // Spaces before an ellipsis are left to the `ellipsis-symbol` rule, and empty
+                    // clauses to the closing mark.
+                    let spaces = text[offset + character.len_utf8()..]
+                        .chars()
+                        .take_while(|space| *space == ' ' || *space == '\u{a0}')
+                        .map(char::len_utf8)
+                        .sum::<usize>();
+                    let after = text[offset + character.len_utf8() + spaces..].chars().next();
+
+                    if spaces > 0 && after.is_some_and(|after| !"…?!".contains(after)) {
+                        let start = offset + character.len_utf8();
+
+                        warnings.push(self.warning(
+                            "Please write `¿` and `¡` right before the first word of the clause, without space.",
+                            offset,
+                            start + spaces,
+                            vec![LinterFix { start, end: start + spaces, replacement: String::new() }],
+                        ));
+                    }
+                }
+                '?' | '!' => {
+                    // A run of marks ends the question or exclamation once (ex: `¡Hola!!`, `¡¿Qué?!`).
+                    let mut marks = vec![character];
+
+                    while let Some(&(_, mark @ ('?' | '!'))) = characters.peek() {
+                        marks.push(mark);
+                        characters.next();
+                    }
+
+                    let end = offset + marks.len();
+                    let next = characters.peek().map(|(_, next)| *next);
+
+                    // Marks within words aren’t punctuation (ex: `/search?q=`).
+                    if next.is_some_and(|next| next.is_alphanumeric() || next == '=') {
+                        continue;
+                    }
+
+                    let level = levels.last_mut().unwrap();
+
+                    if level.openers.is_empty() {
+                        let (openers, message) = if marks.contains(&'?') {
+                            (if marks.contains(&'!') { "¡¿" } else { "¿" }, "Please open the question with `¿`.")
+                        } else {
+                            ("¡", "Please open the exclamation with `¡`.")
+                        };
+                        let start = level.clause_start.unwrap_or(offset);
+
+                        warnings.push(self.warning(
+                            message,
+                            start,
+                            end,
+                            vec![LinterFix { start, end: start, replacement: openers.to_string() }],
+                        ));
+                    }
+
+                    for _ in 0..marks.len().min(level.openers.len()) {
+                        let (opener_offset, opener) = level.openers.pop().unwrap();
+                        let clause_start = level.clause_start.unwrap_or(opener_offset);
+
+                        // `¿` is closed by `?` and `¡` by `!`, both by `?!` or `!?` (ex: `¡¿Qué?!`).
+                        if !marks.contains(&if opener == '¿' { '?' } else { '!' }) {
+                            let message = if opener == '¿' {
+                                "Please end the question opened with `¿` with `?`."
+                            } else {
+                                "Please end the exclamation opened with `¡` with `!`."
+                            };
+
+                            warnings.push(self.warning(message, opener_offset, end, Vec::new()));
+                            continue;
+                        }
+
+                        // The opener of an empty clause has been written after it (ex: `vienes¿?`).
+                        let is_empty = text[opener_offset + opener.len_utf8()..offset].trim().is_empty();
+
+                        if is_empty && clause_start < opener_offset {
+                            warnings.push(self.warning(
+                                "Please write `¿` and `¡` at the start of the clause.",
+                                clause_start,
+                                end,
+                                vec![
+                                    LinterFix { start: clause_start, end: clause_start, replacement: opener.to_string() },
+                                    LinterFix { start: opener_offset, end: offset, replacement: String::new() },
+                                ],
+                            ));
+                        }
+                    }
+
+                    if level.openers.is_empty() {
+                        level.clause_start = None;
+                    }
+                }
+                '«' | '“' | '"' if levels.last().unwrap().quote != Some('"') || character != '"' => {
+                    levels.last_mut().unwrap().clause_start.get_or_insert(offset);
+                    levels.push(Level::new(Some(character)));
+                }
+                '»' | '”' | '"' if levels.len() > 1 => {
+                    let level = levels.pop().unwrap();
+
+                    self.close(level, text, offset, &mut warnings);
+                }
+                // Questions and exclamations can span lines, but not paragraphs.
+                '\n' if text[offset + 1..].trim_start_matches([' ', '\t', '\r']).starts_with('\n') => {
+                    for level in levels.drain(..).rev() {
+                        self.close(level, text, offset, &mut warnings);
+                    }
+
+                    levels.push(Level::new(None));
+                }
+                '.' | '…' | ';' => {
+                    let level = levels.last_mut().unwrap();
+
+                    // Abbreviations can be part of questions (ex: `Dijo el Sr. López?`).
+                    let is_abbreviation = character == '.' && {
+                        let before = text[..offset].trim_end_matches(char::is_alphabetic);
+                        let word = text[before.len()..offset].to_lowercase();
+
+                        word.chars().count() == 1 || ABBREVIATIONS.contains(&word.as_str())
+                    };
+
+                    if level.openers.is_empty() && next.is_none_or(char::is_whitespace) && !is_abbreviation {
+                        level.clause_start = None;
+                    }
+                }
+                // Dialogue dashes and spaces don’t start a clause.
+                '—' | '–' | '-' => {}
+                _ if character.is_whitespace() => {}
+                _ => {
+                    levels.last_mut().unwrap().clause_start.get_or_insert(offset);
+                }
+            }
+        }
+
+        for level in levels.into_iter().rev() {
+            self.close(level, text, text.len(), &mut warnings);
+        }
+
+        warnings.sort_by_key(|warning| warning.start);
+        warnings
+    }
+
+    fn id(&self) -> &'static str {
+        "inverted-punctuation"
+    }
+
+    fn locales(&self) -> Vec<&'static str> {
+        vec!["es"]
+    }
+
+    fn message(&self) -> &'static str {
+        "Please open questions with `¿` and exclamations with `¡`."
+    }
+
+    fn regex_pattern(&self) -> String {
+        "[¿¡?!]".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fix(text: &str) -> String {
+        fixes::apply_fixes(text, &InvertedPunctuationFilter {}.check(text)).text
+    }
+
+    #[test]
+    fn test_filter_with_no_warnings() {
+        let filter = InvertedPunctuationFilter {};
+
+        let warnings = filter.check(
+            "¿Vienes? Juan, ¿vienes mañana? ¡¿Qué dices?! ¿Viene el Sr. López?\n\
+             Me dijo: «¿Vienes?», y ¿dijiste «hola»? Ver https://example.com/?q=1.",
+        );
+
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_filter_with_missing_openers() {
+        let filter = InvertedPunctuationFilter {};
+
+        let warnings = filter.check("Hola. Juan, vienes? —Qué bien!");
+
+        assert_eq!(2, warnings.len());
+        assert_eq!("Please open the question with `¿`.", warnings[0].message);
+        assert_eq!((6, 19), (warnings[0].start, warnings[0].end));
+        assert_eq!("Please open the exclamation with `¡`.", warnings[1].message);
+        assert_eq!("Hola. ¿Juan, vienes? —¡Qué bien!", fix("Hola. Juan, vienes? —Qué bien!"));
+    }
+
+    #[test]
+    fn test_filter_with_unclosed_openers() {
+        let filter = InvertedPunctuationFilter {};
+
+        let warnings = filter.check("¿Vienes mañana.\n\nDijo «¡Hola» y se fue.");
+
+        assert_eq!(2, warnings.len());
+        assert_eq!("Please end the question opened with `¿` with `?`.", warnings[0].message);
+        assert_eq!((0, 17), (warnings[0].start, warnings[0].end));
+        assert_eq!("Please end the exclamation opened with `¡` with `!`.", warnings[1].message);
+        assert_eq!((26, 32), (warnings[1].start, warnings[1].end));
+    }
+
+    #[test]
+    fn test_filter_with_line_breaks() {
+        let filter = InvertedPunctuationFilter {};
+
+        assert!(filter.check("¿Vienes\nmañana a la fiesta?").is_empty());
+        assert_eq!(2, filter.check("¿Vienes\n\nmañana a la fiesta?").len());
+    }
+
+    #[test]
+    fn test_filter_with_runs_of_marks() {
+        let filter = InvertedPunctuationFilter {};
+
+        assert!(filter.check("¡Hola!! ¿Qué tal?? ¡¿Qué dices?!").is_empty());
+        assert_eq!("¡Hola!! ¿Qué tal?", fix("¡Hola!! Qué tal?"));
+        assert_eq!("¡¿Qué dices?!", fix("Qué dices?!"));
+    }
+
+    #[test]
+    fn test_filter_with_mismatched_marks() {
+        let filter = InvertedPunctuationFilter {};
+
+        let warnings = filter.check("¿Vienes! ¡Hola?");
+
+        assert_eq!(2, warnings.len());
+        assert_eq!("Please end the question opened with `¿` with `?`.", warnings[0].message);
+        assert_eq!((0, 9), (warnings[0].start, warnings[0].end));
+        assert_eq!("Please end the exclamation opened with `¡` with `!`.", warnings[1].message);
+        assert_eq!((10, 17), (warnings[1].start, warnings[1].end));
+    }
+
+    #[test]
+    fn test_filter_with_abbreviations() {
+        let filter = InvertedPunctuationFilter {};
+
+        let warnings = filter.check("Hola. Dijo el Sr. López?");
+
+        assert_eq!(1, warnings.len());
+        assert_eq!((6, 25), (warnings[0].start, warnings[0].end));
+        assert_eq!("Hola. ¿Dijo el Sr. López?", fix("Hola. Dijo el Sr. López?"));
+    }
+
+    #[test]
+    fn test_filter_with_misplaced_openers() {
+        assert_eq!("¿Vienes? ¿Juan, vienes?", fix("¿ Vienes? Juan, vienes¿ ?"));
+    }
+}
@@ -1,48 +1,126 @@
 pub mod curly_apostrophe_filter;
+pub mod custom_filter;
+pub mod dash_filter;
 pub mod ellipsis_symbol_filter;
+pub mod inverted_punctuation_filter;
 pub mod no_space_before_comma_filter;
+pub mod number_format_filter;
 pub mod price_filter;
 pub mod quotes_filter;
 pub mod space_before_double_ponctuation_filter;
+pub mod unit_filter;
 
 use super::*;
 
+use regex::Regex;
+
 pub use self::curly_apostrophe_filter::CurlyApostropheFilter;
+pub use self::custom_filter::{CustomFilter, CustomRule};
+pub use self::dash_filter::DashFilter;
 pub use self::ellipsis_symbol_filter::EllipsisSymbolFilter;
+pub use self::inverted_punctuation_filter::InvertedPunctuationFilter;
 pub use self::no_space_before_comma_filter::NoSpaceBeforeCommaFilter;
+pub use self::number_format_filter::NumberFormatFilter;
 pub use self::price_filter::PriceFilter;
 pub use self::quotes_filter::QuotesFilter;
 pub use self::space_before_double_ponctuation_filter::SpaceBeforeDoublePonctuationFilter;
+pub use self::unit_filter::UnitFilter;
 
-pub trait LinterFilter {
-    fn check(&self, text: &str) -> Result<(), Vec<LinterWarning>> {
-        use regex::Regex;
+/// A typographic rule.
+///
+/// Most rules only need to describe what they look for with a regular expression; the default
+/// `check` implementation then raises a warning for each match:
+///
+/// ```
+/// use typographic_linter::errors::LinterFix;
+/// use typographic_linter::{Linter, LinterFilter};
+///
+/// struct ProductNameFilter {}
+///
+/// impl LinterFilter for ProductNameFilter {
+///     fn id(&self) -> &str {
+///         "product-name"
+///     }
+///
+///     fn message(&self) -> &str {
+///         "Please write “GitHub” with a capital H."
+///     }
+///
+///     fn regex_pattern(&self) -> String {
+///         r"\bGithub\b".to_string()
+///     }
+///
+///     fn fixes(&self, _text: &str, start: usize, end: usize) -> Vec<LinterFix> {
+///         vec![LinterFix { start, end, replacement: "GitHub".to_string() }]
+///     }
+/// }
+///
+/// let mut linter = Linter::new("en".to_string()).unwrap();
+///
+/// linter.add_filter(Box::new(ProductNameFilter {})).unwrap();
+///
+/// assert_eq!("It’s on GitHub.", linter.fix("It’s on Github."));
+/// ```
+pub trait LinterFilter: Send + Sync {
+    /// Returns the warnings raised on `text`. Filters whose `regex_pattern` doesn’t compile raise
+    /// no warnings, and panic in debug builds: `Linter::add_filter` and `LinterBuilder::build` report
+    /// them as `LinterError::InvalidRegex`.
+    fn check(&self, text: &str) -> Vec<LinterWarning> {
+        let regex = Regex::new(self.regex_pattern().as_str());
 
-        let mut warnings = Vec::<LinterWarning>::new();
+        debug_assert!(regex.is_ok(), "invalid pattern for rule `{}`", self.id());
 
-        let results = Regex::new(self.regex_pattern().as_str()).unwrap();
+        match regex {
+            Ok(regex) => self.check_with_regex(&regex, text),
+            Err(_) => Vec::new(),
+        }
+    }
 
-        for result in results.find_iter(text) {
+    /// Returns the warnings raised on `text`, given the compiled `regex_pattern`. The `Linter`
+    /// compiles it once and only calls this method when it matches `text`, so filters with custom
+    /// logic must not raise warnings on texts that don’t match their regular expression.
+    fn check_with_regex(&self, regex: &Regex, text: &str) -> Vec<LinterWarning> {
+        let mut warnings = Vec::<LinterWarning>::new();
+
+        for result in regex.find_iter(text) {
             warnings.push(
                 LinterWarning {
-                    message: self.message(),
+                    rule: self.id().to_string(),
+                    severity: self.severity(),
+                    message: self.message().to_string(),
                     start: result.start(),
                     end: result.end(),
+                    fixes: self.fixes(text, result.start(), result.end()),
+                    ..Default::default()
                 }
             );
         }
 
-        if warnings.is_empty() {
-            Ok(())
-        } else {
-            Err(warnings)
-        }
+        warnings
     }
 
-    fn locales(&self) -> Vec<&'static str> {
+    /// Returns the stable identifier of the rule, in kebab case (ex: `curly-apostrophe`).
+    fn id(&self) -> &str;
+
+    /// Returns the locales the rule applies to, or none if it applies to all of them.
+    fn locales(&self) -> Vec<&str> {
         Vec::new()
     }
 
-    fn message(&self) -> &'static str;
+    /// Returns the default severity of the warnings, which can be overridden per `Linter`.
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    /// Returns the edits fixing the match found between the `start` and `end` byte offsets of
+    /// `text`. Filters that can’t safely suggest a fix return no edit.
+    fn fixes(&self, _text: &str, _start: usize, _end: usize) -> Vec<LinterFix> {
+        Vec::new()
+    }
+
+    /// Returns the message of the warnings.
+    fn message(&self) -> &str;
+
+    /// Returns the regular expression matching the text to warn about.
     fn regex_pattern(&self) -> String;
 }
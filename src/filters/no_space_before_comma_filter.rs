@@ -3,6 +3,14 @@ use super::*;
 pub struct NoSpaceBeforeCommaFilter {}
 
 impl LinterFilter for NoSpaceBeforeCommaFilter {
+    fn fixes(&self, _text: &str, start: usize, end: usize) -> Vec<LinterFix> {
+        vec![LinterFix { start, end, replacement: ",".to_string() }]
+    }
+
+    fn id(&self) -> &'static str {
+        "no-space-before-comma"
+    }
+
     fn message(&self) -> &'static str {
         "Please don’t use a space before a comma."
     }
@@ -10,6 +18,10 @@ impl LinterFilter for NoSpaceBeforeCommaFilter {
     fn regex_pattern(&self) -> String {
         r"\s+,".to_string()
     }
+
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
 }
 
 #[cfg(test)]
@@ -20,26 +32,32 @@ mod tests {
     fn test_filter_with_no_warnings() {
         let filter = NoSpaceBeforeCommaFilter {};
 
-        let result = filter.check("Lorsqu’on le lui demande, il répond qu’il se nomme Simbad le marin.");
+        let warnings = filter.check("Lorsqu’on le lui demande, il répond qu’il se nomme Simbad le marin.");
 
-        assert_eq!(false, result.is_err());
-        assert_eq!((), result.unwrap());
+        assert!(warnings.is_empty());
     }
 
     #[test]
     fn test_filter_with_a_warning() {
         let filter = NoSpaceBeforeCommaFilter {};
 
-        let result = filter.check("Lorsqu’on le lui demande , il répond qu’il se nomme Simbad le marin.");
-
-        assert!(result.is_err());
-
-        let warnings = result.err().unwrap();
+        let warnings = filter.check("Lorsqu’on le lui demande , il répond qu’il se nomme Simbad le marin.");
 
         assert_eq!(1, warnings.len());
         assert_eq!("Please don’t use a space before a comma.", warnings[0].message);
         assert_eq!(26, warnings[0].start);
         assert_eq!(28, warnings[0].end);
     }
-}
 
+    #[test]
+    fn test_filter_fixes() {
+        let filter = NoSpaceBeforeCommaFilter {};
+
+        let warnings = filter.check("Lorsqu’on le lui demande , il répond.");
+
+        assert_eq!(
+            vec![LinterFix { start: 26, end: 28, replacement: ",".to_string() }],
+            warnings[0].fixes
+        );
+    }
+}
@@ -0,0 +1,227 @@
+use super::*;
+
+use number::{Number, NUMBER};
+
+/// Words introducing identifiers or versions, whose digits aren’t quantities (ex: `ZIP 90210`).
+const LABELS: &[&str] = &[
+    "#", "id", "isbn", "n°", "no", "nº", "nr", "plz", "ref", "tel", "v", "version", "versión", "versione", "zip",
+];
+
+pub struct NumberFormatFilter {
+    pub locale: Locale,
+    pub spacing: FrenchSpacing,
+}
+
+impl NumberFormatFilter {
+    /// Returns the decimal mark of the locale.
+    fn decimal_mark(&self) -> char {
+        match self.locale.language() {
+            Language::En => '.',
+            Language::De | Language::Es | Language::Fr | Language::It => ',',
+        }
+    }
+
+    /// Returns the thousands separators accepted in the locale, the first one being used by fixes.
+    fn group_separators(&self) -> Vec<char> {
+        match self.locale.language() {
+            Language::De | Language::Es | Language::It => vec!['.'],
+            Language::En => vec![','],
+            Language::Fr => {
+                let expected = self.spacing.space('0');
+                let mut separators = vec![expected];
+
+                separators.extend(self.spacing.accepted_spaces('0').chars().filter(|space| *space != expected));
+                separators
+            }
+        }
+    }
+
+    /// Returns whether a number is rather a time written with a dot (ex: `um 10.30 Uhr`).
+    fn is_time(&self, number: &Number) -> bool {
+        let is_clock =
+            |digits: &str, max: u32| digits.len() <= 2 && digits.parse::<u32>().is_ok_and(|value| value <= max);
+
+        self.decimal_mark() != '.'
+            && !number.is_grouped
+            && number.fraction.as_ref().is_some_and(|minutes| minutes.len() == 2 && is_clock(minutes, 59))
+            && is_clock(&number.integer, 23)
+    }
+}
+
+/// Returns whether the word before `start` introduces an identifier or a version.
+fn follows_label(text: &str, start: usize) -> bool {
+    let before = text[..start].trim_end_matches([' ', '\u{a0}', '\u{202f}', '.', ':']);
+    let word = &before[before.trim_end_matches(|character: char| !character.is_whitespace()).len()..];
+
+    LABELS.contains(&word.to_lowercase().as_str())
+}
+
+impl LinterFilter for NumberFormatFilter {
+    fn check_with_regex(&self, regex: &Regex, text: &str) -> Vec<LinterWarning> {
+        let separators = self.group_separators();
+
+        regex
+            .find_iter(text)
+            .filter_map(|found| {
+                // Versions, identifiers or references (ex: `v1.2.3`, `A320`) aren’t numbers.
+                let previous = text[..found.start()].chars().next_back();
+
+                if previous.is_some_and(|previous| previous.is_alphanumeric() || "_.,".contains(previous)) {
+                    return None;
+                }
+
+                // Nor are dates or numbers followed by letters (ex: `12.05.2023`, `3D`).
+                let mut next = text[found.end()..].chars();
+                let is_followed = match next.next() {
+                    Some('.' | ',') => next.next().is_some_and(|digit| digit.is_ascii_digit()),
+                    Some(next) => next.is_alphanumeric(),
+                    None => false,
+                };
+
+                if is_followed || follows_label(text, found.start()) {
+                    return None;
+                }
+
+                let has_spaced_groups = self.locale.language() == Language::Fr;
+                let number = Number::parse(found.as_str(), self.decimal_mark(), has_spaced_groups)?;
+
+                // Nor are codes starting with zeros (ex: `007`) or times.
+                if (number.integer.len() > 1 && number.integer.starts_with('0')) || self.is_time(&number) {
+                    return None;
+                }
+
+                let formats = separators
+                    .iter()
+                    .map(|separator| number.format(*separator, self.decimal_mark()))
+                    .collect::<Vec<String>>();
+
+                if formats.iter().any(|format| format == found.as_str()) {
+                    return None;
+                }
+
+                Some(LinterWarning {
+                    rule: self.id().to_string(),
+                    severity: self.severity(),
+                    message: self.message().to_string(),
+                    start: found.start(),
+                    end: found.end(),
+                    fixes: vec![LinterFix { start: found.start(), end: found.end(), replacement: formats[0].clone() }],
+                    ..Default::default()
+                })
+            })
+            .collect()
+    }
+
+    fn id(&self) -> &'static str {
+        "number-format"
+    }
+
+    fn message(&self) -> &'static str {
+        match (self.locale.language(), self.spacing) {
+            (Language::De | Language::Es | Language::It, _) => {
+                "Please group the digits of long numbers by three with dots, and use a decimal comma."
+            }
+            (Language::En, _) => {
+                "Please group the digits of long numbers by three with commas, and use a decimal point."
+            }
+            (Language::Fr, FrenchSpacing::Strict) => {
+                "Please group the digits of long numbers by three with narrow non-breaking spaces, and use a decimal comma."
+            }
+            (Language::Fr, FrenchSpacing::Lenient) => {
+                "Please group the digits of long numbers by three with non-breaking spaces, and use a decimal comma."
+            }
+            (Language::Fr, FrenchSpacing::WebSafe) => {
+                "Please group the digits of long numbers by three with non-breaking spaces (U+00A0), and use a decimal comma."
+            }
+        }
+    }
+
+    fn regex_pattern(&self) -> String {
+        NUMBER.to_string()
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Info
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(locale: &str, spacing: FrenchSpacing) -> NumberFormatFilter {
+        NumberFormatFilter { locale: locale.parse().unwrap(), spacing }
+    }
+
+    fn replacements(filter: &NumberFormatFilter, text: &str) -> Vec<String> {
+        filter
+            .check(text)
+            .into_iter()
+            .map(|warning| warning.fixes[0].replacement.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_number_format_filter_with_english() {
+        let filter = filter("en", FrenchSpacing::Lenient);
+
+        assert_eq!(
+            vec!["1,200.50", "12,345", "1,000,000"],
+            replacements(&filter, "1.200,50 or 12345 or 1 000 000, not 1,200 or 1200 or 3.14 or 2024.")
+        );
+        assert!(filter.check("Version 1.2.3, v2.0, A320, 007 and 1,200,000.5 people.").is_empty());
+    }
+
+    #[test]
+    fn test_number_format_filter_with_german() {
+        let filter = filter("de", FrenchSpacing::Lenient);
+
+        let warnings = filter.check("Es kostet 1,200.50 oder 3.5, nicht 1.200 oder 3,5.");
+
+        assert_eq!(2, warnings.len());
+        assert_eq!(
+            "Please group the digits of long numbers by three with dots, and use a decimal comma.",
+            warnings[0].message
+        );
+        assert_eq!(
+            vec![LinterFix { start: 10, end: 18, replacement: "1.200,50".to_string() }],
+            warnings[0].fixes
+        );
+        assert_eq!("3,5", warnings[1].fixes[0].replacement);
+    }
+
+    #[test]
+    fn test_number_format_filter_with_dates_times_and_versions() {
+        let german = filter("de", FrenchSpacing::Lenient);
+        let french = filter("fr", FrenchSpacing::Lenient);
+        let english = filter("en", FrenchSpacing::Lenient);
+
+        assert!(german.check("Am 12.05.2023 um 10.30 Uhr, am 12.05. um 9.45 Uhr.").is_empty());
+        assert!(french.check("La version 2.0 du 12.05.2023, v3.1 ou 2.0.1.").is_empty());
+        assert!(english.check("ZIP 90210, order #123456, No. 12345 and a 3D printer.").is_empty());
+        assert_eq!(vec!["3,5"], replacements(&german, "Es sind 3.5 Liter."));
+    }
+
+    #[test]
+    fn test_number_format_filter_with_french() {
+        let text = "1,200.50 ou 12345 ou 1\u{a0}200 ou 1\u{202f}200 ou 1 200 000, pas 1,5.";
+
+        assert_eq!(
+            vec!["1\u{a0}200,50", "12\u{a0}345", "1\u{a0}200\u{a0}000"],
+            replacements(&filter("fr", FrenchSpacing::Lenient), text)
+        );
+        assert_eq!(
+            vec!["1\u{202f}200,50", "12\u{202f}345", "1\u{202f}200", "1\u{202f}200\u{202f}000"],
+            replacements(&filter("fr", FrenchSpacing::Strict), text)
+        );
+    }
+
+    #[test]
+    fn test_number_format_filter_with_french_and_ordinary_spaces() {
+        assert_eq!(
+            vec!["1\u{a0}200,50", "3\u{a0}100"],
+            replacements(&filter("fr", FrenchSpacing::Lenient), "1 200,50 ou 3 100 pages")
+        );
+        assert_eq!(vec!["1\u{202f}200,50"], replacements(&filter("fr", FrenchSpacing::Strict), "1 200,50"));
+    }
+}
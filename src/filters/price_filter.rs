@@ -1,7 +1,10 @@
 use super::*;
 
+use number::{NUMBER, NUMBER_REGEX};
+
 pub struct PriceFilter {
-    pub locale: String,
+    pub locale: Locale,
+    pub spacing: FrenchSpacing,
 }
 
 impl PriceFilter {
@@ -11,43 +14,81 @@ impl PriceFilter {
 }
 
 impl LinterFilter for PriceFilter {
+    fn fixes(&self, text: &str, start: usize, end: usize) -> Vec<LinterFix> {
+        let price = &text[start..end];
+
+        let amount = match NUMBER_REGEX.find(price) {
+            Some(amount) => amount.as_str(),
+            None => return Vec::new(),
+        };
+        let currency = match price.chars().find(|character| self.currencies().contains(*character)) {
+            Some(currency) => currency,
+            None => return Vec::new(),
+        };
+
+        // Anything else than the amount, the currency and spaces can’t be safely dropped.
+        let is_safe = price.replacen(amount, "", 1).chars().all(|character| {
+            character.is_whitespace() || character == currency
+        });
+
+        if !is_safe {
+            return Vec::new();
+        }
+
+        let replacement = match self.locale.language() {
+            Language::De | Language::Es => format!("{}\u{a0}{}", amount, currency),
+            Language::Fr => format!("{}{}{}", amount, self.spacing.space(currency), currency),
+            Language::En => format!("{}{}", currency, amount),
+            Language::It => format!("{}\u{a0}{}", currency, amount),
+        };
+
+        vec![LinterFix { start, end, replacement }]
+    }
+
+    fn id(&self) -> &'static str {
+        "price"
+    }
+
     fn locales(&self) -> Vec<&'static str> {
         vec!("de", "en", "es", "fr")
     }
 
     fn message(&self) -> &'static str {
-        match &self.locale as &str {
-            "de" | "es" | "fr" => "The currency sign should be written after the amount and a non-breaking space.",
-            "en" => "The currency sign should be written before the amount without space.",
-            "it" => "The currency sign should be written before the amount and a non-breaking space.",
-            _ => unimplemented!(),
+        match self.locale.language() {
+            Language::Fr if self.spacing != FrenchSpacing::Lenient => {
+                "The currency sign should be written after the amount and a non-breaking space (U+00A0)."
+            }
+            Language::De | Language::Es | Language::Fr => "The currency sign should be written after the amount and a non-breaking space.",
+            Language::En => "The currency sign should be written before the amount without space.",
+            Language::It => "The currency sign should be written before the amount and a non-breaking space.",
         }
     }
 
     fn regex_pattern(&self) -> String {
-        match &self.locale as &str {
+        match self.locale.language() {
             // Matches one of the following:
             // - digits followed by a character (or none) other than a non-breaking space followed
-            //   by any of currencies() return values (ex: `120€` or `120 $`);
+            //   by any of currencies() return values (ex: `120€`, `120 $` or `1 200,50 $`);
             // - any of currencies() return values followed by any whitespace character (or not)
             //   followed by digits (ex: `€120` or `$ 120`).
-            "de" | "es" | "fr" => format!("([\\d]+[^ ]?[{}]{{1}}|[{}]{{1}}[\\s]?[\\d]+)", self.currencies(), self.currencies()),
+            Language::De | Language::Es => format!("((?:{number})[^ ]?[{}]{{1}}|[{}]{{1}}[\\s]?(?:{number}))", self.currencies(), self.currencies(), number = NUMBER),
+
+            // Same as above, with the non-breaking spaces accepted by the French spacing policy.
+            Language::Fr => format!("((?:{number})[^{}]?[{}]{{1}}|[{}]{{1}}[\\s]?(?:{number}))", self.spacing.accepted_spaces('€'), self.currencies(), self.currencies(), number = NUMBER),
 
             // Matches one of the following:
             // - digits followed by any whitespace character (or not) followed by any of
             //   currencies() return values (ex: `120€` or `120 €`)
             // - any of currencies() return values followed by any whitespace character followed by
             //   digits (ex: `€ 120` or `$ 120`).
-            "en" => format!("([\\d]+[\\s]?[{}]{{1}}|[{}]{{1}}[\\s][\\d]+)", self.currencies(), self.currencies()),
+            Language::En => format!("((?:{number})[\\s]?[{}]{{1}}|[{}]{{1}}[\\s](?:{number}))", self.currencies(), self.currencies(), number = NUMBER),
 
             // Matches one of the following:
             // - digits followed by any whitespace character (or not) followed by any of
             //   currencies() return values (ex: `120€` or `120 €`)
             // - any of currencies() return values followed by a character (or none) other than a
             //   non-breaking space followed by digits (ex: `€ 120` or `$120`).
-            "it" => format!("([\\d]+[\\s]?[{}]{{1}}|[{}]{{1}}[^ ]?[\\d]+)", self.currencies(), self.currencies()),
-
-            _ => unimplemented!(),
+            Language::It => format!("((?:{number})[\\s]?[{}]{{1}}|[{}]{{1}}[^ ]?(?:{number}))", self.currencies(), self.currencies(), number = NUMBER),
         }
     }
 }
@@ -94,13 +135,12 @@ mod tests {
     #[test]
     fn test_filters_when_warnings() {
         for expected_warning in expected_warnings() {
-            let filter = PriceFilter { locale: expected_warning.locale.to_string() };
-
-            let result = filter.check(expected_warning.text);
-
-            assert!(result.is_err());
+            let filter = PriceFilter {
+                locale: expected_warning.locale.parse().unwrap(),
+                spacing: FrenchSpacing::Lenient,
+            };
 
-            let warnings = result.err().unwrap();
+            let warnings = filter.check(expected_warning.text);
 
             assert_eq!(1, warnings.len());
             assert_eq!(expected_warning.start, warnings[0].start);
@@ -108,54 +148,100 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_fixes() {
+        let filter = PriceFilter { locale: "fr".parse().unwrap(), spacing: FrenchSpacing::Lenient };
+
+        let warnings = filter.check("€ 120");
+
+        assert_eq!(
+            vec![LinterFix { start: 0, end: 7, replacement: "120\u{a0}€".to_string() }],
+            warnings[0].fixes
+        );
+
+        let filter = PriceFilter { locale: "en".parse().unwrap(), spacing: FrenchSpacing::Lenient };
+
+        let warnings = filter.check("120 €");
+
+        assert_eq!(
+            vec![LinterFix { start: 0, end: 7, replacement: "€120".to_string() }],
+            warnings[0].fixes
+        );
+    }
+
     #[test]
     fn test_filter_when_de_and_no_warnings() {
-        let filter = PriceFilter { locale: "de".to_string() };
+        let filter = PriceFilter { locale: "de".parse().unwrap(), spacing: FrenchSpacing::Lenient };
 
         let result = filter.check("120 €");
 
-        assert_eq!(false, result.is_err());
-        assert_eq!((), result.unwrap());
+        assert!(result.is_empty());
     }
 
     #[test]
     fn test_filter_when_en_and_no_warnings() {
-        let filter = PriceFilter { locale: "en".to_string() };
+        let filter = PriceFilter { locale: "en".parse().unwrap(), spacing: FrenchSpacing::Lenient };
 
         let result = filter.check("€120");
 
-        assert_eq!(false, result.is_err());
-        assert_eq!((), result.unwrap());
+        assert!(result.is_empty());
     }
 
     #[test]
     fn test_filter_when_es_and_no_warnings() {
-        let filter = PriceFilter { locale: "es".to_string() };
+        let filter = PriceFilter { locale: "es".parse().unwrap(), spacing: FrenchSpacing::Lenient };
 
         let result = filter.check("120 €");
 
-        assert_eq!(false, result.is_err());
-        assert_eq!((), result.unwrap());
+        assert!(result.is_empty());
     }
 
     #[test]
     fn test_filter_when_it_and_no_warnings() {
-        let filter = PriceFilter { locale: "it".to_string() };
+        let filter = PriceFilter { locale: "it".parse().unwrap(), spacing: FrenchSpacing::Lenient };
 
         let result = filter.check("€ 120");
 
-        assert_eq!(false, result.is_err());
-        assert_eq!((), result.unwrap());
+        assert!(result.is_empty());
     }
 
     #[test]
     fn test_filter_when_fr_and_no_warnings() {
-        let filter = PriceFilter { locale: "fr".to_string() };
+        let filter = PriceFilter { locale: "fr".parse().unwrap(), spacing: FrenchSpacing::Lenient };
 
         let result = filter.check("120 €");
 
-        assert_eq!(false, result.is_err());
-        assert_eq!((), result.unwrap());
+        assert!(result.is_empty());
+        assert!(filter.check("120\u{202f}€").is_empty());
+    }
+
+    #[test]
+    fn test_fixes_when_fr_and_web_safe_spacing() {
+        let filter = PriceFilter { locale: "fr".parse().unwrap(), spacing: FrenchSpacing::WebSafe };
+
+        let warnings = filter.check("120\u{202f}€ ou 120\u{a0}€");
+
+        assert_eq!(1, warnings.len());
+        assert_eq!(
+            vec![LinterFix { start: 0, end: 9, replacement: "120\u{a0}€".to_string() }],
+            warnings[0].fixes
+        );
     }
-}
 
+    #[test]
+    fn test_fixes_with_grouped_amounts() {
+        let filter = PriceFilter { locale: "fr".parse().unwrap(), spacing: FrenchSpacing::Lenient };
+
+        let warnings = filter.check("1\u{a0}200,50 $ et $1,200.50");
+
+        assert_eq!(2, warnings.len());
+        assert_eq!(
+            vec![LinterFix { start: 0, end: 11, replacement: "1\u{a0}200,50\u{a0}$".to_string() }],
+            warnings[0].fixes
+        );
+        assert_eq!(
+            vec![LinterFix { start: 15, end: 24, replacement: "1,200.50\u{a0}$".to_string() }],
+            warnings[1].fixes
+        );
+    }
+}
@@ -1,10 +1,57 @@
 use super::*;
 
 pub struct QuotesFilter {
-    pub locale: String,
+    pub locale: Locale,
+    pub spacing: FrenchSpacing,
+}
+
+impl QuotesFilter {
+    /// Returns the opening and closing quotation marks of the locale, spaces included.
+    fn quotation_marks(&self) -> (String, String) {
+        match self.locale.language() {
+            Language::De => ("„".to_string(), "“".to_string()),
+            Language::En => ("“".to_string(), "”".to_string()),
+            Language::Es | Language::It => ("«".to_string(), "»".to_string()),
+            Language::Fr => (format!("«{}", self.spacing.space('«')), format!("{}»", self.spacing.space('»'))),
+        }
+    }
 }
 
 impl LinterFilter for QuotesFilter {
+    fn fixes(&self, text: &str, start: usize, end: usize) -> Vec<LinterFix> {
+        let (opening_mark, closing_mark) = self.quotation_marks();
+        let quotation = &text[start..end];
+
+        // Each edit covers a quotation mark and the spaces between it and the quoted text, so
+        // that the quoted text itself is left untouched for other filters.
+        let opening_end = end - quotation
+            .chars()
+            .skip(1)
+            .skip_while(|character| character.is_whitespace())
+            .map(char::len_utf8)
+            .sum::<usize>();
+        let closing_start = start + quotation
+            .chars()
+            .rev()
+            .skip(1)
+            .skip_while(|character| character.is_whitespace())
+            .map(char::len_utf8)
+            .sum::<usize>();
+
+        if opening_end > closing_start {
+            return Vec::new();
+        }
+
+        vec![
+            LinterFix { start, end: opening_end, replacement: opening_mark },
+            LinterFix { start: closing_start, end, replacement: closing_mark },
+        ]
+    }
+
+    fn id(&self) -> &'static str {
+        "quotes"
+    }
+
     fn locales(&self) -> Vec<&'static str> {
         vec!(
             "de",
@@ -16,26 +63,31 @@ impl LinterFilter for QuotesFilter {
     }
 
     fn message(&self) -> &'static str {
-        match &self.locale as &str {
-            "de" => "Please use german quotation marks without spaces.",
-            "en" => "Please use english double quotation marks without spaces.",
-            "es" | "it" => "Please use french quotation marks without spaces.",
-            "fr" => "Please use french quotation marks with non-breaking spaces.",
-            _ => unimplemented!(),
+        match self.locale.language() {
+            Language::De => "Please use german quotation marks without spaces.",
+            Language::En => "Please use english double quotation marks without spaces.",
+            Language::Es | Language::It => "Please use french quotation marks without spaces.",
+            Language::Fr if self.spacing == FrenchSpacing::Lenient => {
+                "Please use french quotation marks with non-breaking spaces."
+            }
+            Language::Fr => "Please use french quotation marks with non-breaking spaces (U+00A0).",
         }
     }
 
     fn regex_pattern(&self) -> String {
-        let pattern = match &self.locale as &str {
-            "de" => "(\".+\")|(«.+»)|(“.+”)|(„[\\s].+[\\s]“)",
-            "en" => "(\".+\")|(«.+»)|(“[\\s].+[\\s]”)|(„.+“)",
-            "es" => "(\".+\")|(«[\\s].+[\\s]»)|(“.+”)|(„.+“)",
-            "fr" => "(\".+\")|(«[^ ].+[^ ]»)|(“.+”)|(„.+“)",
-            "it" => "(\".+\")|(«\\s.+\\s»)|(“.+”)|(„.+“)",
-            _ => unimplemented!(),
-        };
-
-        pattern.to_string()
+        // Quoted texts can’t contain their own quotation marks, so that each quotation of a
+        // sentence is matched on its own, but can wrap onto another line.
+        match self.locale.language() {
+            Language::De => "(\"[^\"]+\")|(«[^«»]+»)|(“[^“”]+”)|(„\\s[^„“]+\\s“)".to_string(),
+            Language::En => "(\"[^\"]+\")|(«[^«»]+»)|(“\\s[^“”]+\\s”)|(„[^„“]+“)".to_string(),
+            Language::Es => "(\"[^\"]+\")|(«\\s[^«»]+\\s»)|(“[^“”]+”)|(„[^„“]+“)".to_string(),
+            Language::Fr => format!(
+                "(\"[^\"]+\")|(«[^{}«»][^«»]*[^{}«»]»)|(“[^“”]+”)|(„[^„“]+“)",
+                self.spacing.accepted_spaces('«'),
+                self.spacing.accepted_spaces('»')
+            ),
+            Language::It => "(\"[^\"]+\")|(«\\s[^«»]+\\s»)|(“[^“”]+”)|(„[^„“]+“)".to_string(),
+        }
     }
 }
 
@@ -45,13 +97,9 @@ mod tests {
 
     #[test]
     fn test_filters_when_de_and_straight_quotation_marks() {
-        let filter = QuotesFilter { locale: "de".to_string() };
-
-        let result = filter.check("\"Ich auch\", sagte der italienische");
+        let filter = QuotesFilter { locale: "de".parse().unwrap(), spacing: FrenchSpacing::Lenient };
 
-        assert!(result.is_err());
-
-        let warnings = result.err().unwrap();
+        let warnings = filter.check("\"Ich auch\", sagte der italienische");
 
         assert_eq!(1, warnings.len());
         assert_eq!("Please use german quotation marks without spaces.", warnings[0].message);
@@ -61,13 +109,9 @@ mod tests {
 
     #[test]
     fn test_filters_when_de_and_french_quotation_marks() {
-        let filter = QuotesFilter { locale: "de".to_string() };
-
-        let result = filter.check("«Ich auch», sagte der italienische");
+        let filter = QuotesFilter { locale: "de".parse().unwrap(), spacing: FrenchSpacing::Lenient };
 
-        assert!(result.is_err());
-
-        let warnings = result.err().unwrap();
+        let warnings = filter.check("«Ich auch», sagte der italienische");
 
         assert_eq!(1, warnings.len());
         assert_eq!("Please use german quotation marks without spaces.", warnings[0].message);
@@ -77,13 +121,9 @@ mod tests {
 
     #[test]
     fn test_filters_when_de_and_english_quotation_marks() {
-        let filter = QuotesFilter { locale: "de".to_string() };
-
-        let result = filter.check("“Ich auch”, sagte der italienische");
+        let filter = QuotesFilter { locale: "de".parse().unwrap(), spacing: FrenchSpacing::Lenient };
 
-        assert!(result.is_err());
-
-        let warnings = result.err().unwrap();
+        let warnings = filter.check("“Ich auch”, sagte der italienische");
 
         assert_eq!(1, warnings.len());
         assert_eq!("Please use german quotation marks without spaces.", warnings[0].message);
@@ -93,13 +133,9 @@ mod tests {
 
     #[test]
     fn test_filters_when_de_and_german_quotation_marks_and_spaces() {
-        let filter = QuotesFilter { locale: "de".to_string() };
-
-        let result = filter.check("„ Ich auch “, sagte der italienische");
-
-        assert!(result.is_err());
+        let filter = QuotesFilter { locale: "de".parse().unwrap(), spacing: FrenchSpacing::Lenient };
 
-        let warnings = result.err().unwrap();
+        let warnings = filter.check("„ Ich auch “, sagte der italienische");
 
         assert_eq!(1, warnings.len());
         assert_eq!("Please use german quotation marks without spaces.", warnings[0].message);
@@ -109,23 +145,18 @@ mod tests {
 
     #[test]
     fn test_filter_when_de_and_no_warnings() {
-        let filter = QuotesFilter { locale: "de".to_string() };
+        let filter = QuotesFilter { locale: "de".parse().unwrap(), spacing: FrenchSpacing::Lenient };
 
         let result = filter.check("„Ich auch“, sagte der italienische");
 
-        assert_eq!(false, result.is_err());
-        assert_eq!((), result.unwrap());
+        assert!(result.is_empty());
     }
 
     #[test]
     fn test_filters_when_es_and_straight_quotation_marks() {
-        let filter = QuotesFilter { locale: "es".to_string() };
-
-        let result = filter.check("\"Y yo también\", dijo el italiano");
-
-        assert!(result.is_err());
+        let filter = QuotesFilter { locale: "es".parse().unwrap(), spacing: FrenchSpacing::Lenient };
 
-        let warnings = result.err().unwrap();
+        let warnings = filter.check("\"Y yo también\", dijo el italiano");
 
         assert_eq!(1, warnings.len());
         assert_eq!("Please use french quotation marks without spaces.", warnings[0].message);
@@ -135,13 +166,9 @@ mod tests {
 
     #[test]
     fn test_filters_when_es_and_english_quotation_marks() {
-        let filter = QuotesFilter { locale: "es".to_string() };
+        let filter = QuotesFilter { locale: "es".parse().unwrap(), spacing: FrenchSpacing::Lenient };
 
-        let result = filter.check("“Y yo también”, dijo el italiano");
-
-        assert!(result.is_err());
-
-        let warnings = result.err().unwrap();
+        let warnings = filter.check("“Y yo también”, dijo el italiano");
 
         assert_eq!(1, warnings.len());
         assert_eq!("Please use french quotation marks without spaces.", warnings[0].message);
@@ -151,13 +178,9 @@ mod tests {
 
     #[test]
     fn test_filters_when_es_and_french_quotation_marks_with_spaces() {
-        let filter = QuotesFilter { locale: "es".to_string() };
-
-        let result = filter.check("« Y yo también », dijo el italiano");
-
-        assert!(result.is_err());
+        let filter = QuotesFilter { locale: "es".parse().unwrap(), spacing: FrenchSpacing::Lenient };
 
-        let warnings = result.err().unwrap();
+        let warnings = filter.check("« Y yo también », dijo el italiano");
 
         assert_eq!(1, warnings.len());
         assert_eq!("Please use french quotation marks without spaces.", warnings[0].message);
@@ -167,23 +190,18 @@ mod tests {
 
     #[test]
     fn test_filter_when_es_and_no_warnings() {
-        let filter = QuotesFilter { locale: "es".to_string() };
+        let filter = QuotesFilter { locale: "es".parse().unwrap(), spacing: FrenchSpacing::Lenient };
 
         let result = filter.check("«Y yo también», dijo el italiano");
 
-        assert_eq!(false, result.is_err());
-        assert_eq!((), result.unwrap());
+        assert!(result.is_empty());
     }
 
     #[test]
     fn test_filters_when_en_and_and_straight_quotation_marks() {
-        let filter = QuotesFilter { locale: "en".to_string() };
+        let filter = QuotesFilter { locale: "en".parse().unwrap(), spacing: FrenchSpacing::Lenient };
 
-        let result = filter.check("\"Mee too\", said the French.");
-
-        assert!(result.is_err());
-
-        let warnings = result.err().unwrap();
+        let warnings = filter.check("\"Mee too\", said the French.");
 
         assert_eq!(1, warnings.len());
         assert_eq!("Please use english double quotation marks without spaces.", warnings[0].message);
@@ -193,13 +211,9 @@ mod tests {
 
     #[test]
     fn test_filters_when_en_and_and_french_quotation_marks() {
-        let filter = QuotesFilter { locale: "en".to_string() };
-
-        let result = filter.check("« Mee too », said the French.");
-
-        assert!(result.is_err());
+        let filter = QuotesFilter { locale: "en".parse().unwrap(), spacing: FrenchSpacing::Lenient };
 
-        let warnings = result.err().unwrap();
+        let warnings = filter.check("« Mee too », said the French.");
 
         assert_eq!(1, warnings.len());
         assert_eq!("Please use english double quotation marks without spaces.", warnings[0].message);
@@ -209,13 +223,9 @@ mod tests {
 
     #[test]
     fn test_filters_when_en_and_and_english_quotation_marks_with_spaces() {
-        let filter = QuotesFilter { locale: "en".to_string() };
+        let filter = QuotesFilter { locale: "en".parse().unwrap(), spacing: FrenchSpacing::Lenient };
 
-        let result = filter.check("“ Mee too ”, said the French.");
-
-        assert!(result.is_err());
-
-        let warnings = result.err().unwrap();
+        let warnings = filter.check("“ Mee too ”, said the French.");
 
         assert_eq!(1, warnings.len());
         assert_eq!("Please use english double quotation marks without spaces.", warnings[0].message);
@@ -225,13 +235,9 @@ mod tests {
 
     #[test]
     fn test_filters_when_en_and_and_german_quotation_marks() {
-        let filter = QuotesFilter { locale: "en".to_string() };
-
-        let result = filter.check("„Mee too“, said the French.");
-
-        assert!(result.is_err());
+        let filter = QuotesFilter { locale: "en".parse().unwrap(), spacing: FrenchSpacing::Lenient };
 
-        let warnings = result.err().unwrap();
+        let warnings = filter.check("„Mee too“, said the French.");
 
         assert_eq!(1, warnings.len());
         assert_eq!("Please use english double quotation marks without spaces.", warnings[0].message);
@@ -241,23 +247,18 @@ mod tests {
 
     #[test]
     fn test_filter_when_en_and_no_warnings() {
-        let filter = QuotesFilter { locale: "en".to_string() };
+        let filter = QuotesFilter { locale: "en".parse().unwrap(), spacing: FrenchSpacing::Lenient };
 
         let result = filter.check("“Mee too”, said the French.");
 
-        assert_eq!(false, result.is_err());
-        assert_eq!((), result.unwrap());
+        assert!(result.is_empty());
     }
 
     #[test]
     fn test_filters_when_fr_and_straight_quotation_marks() {
-        let filter = QuotesFilter { locale: "fr".to_string() };
+        let filter = QuotesFilter { locale: "fr".parse().unwrap(), spacing: FrenchSpacing::Lenient };
 
-        let result = filter.check("\"Et moi aussi\", dit l’Anglais.");
-
-        assert!(result.is_err());
-
-        let warnings = result.err().unwrap();
+        let warnings = filter.check("\"Et moi aussi\", dit l’Anglais.");
 
         assert_eq!(1, warnings.len());
         assert_eq!("Please use french quotation marks with non-breaking spaces.", warnings[0].message);
@@ -267,13 +268,9 @@ mod tests {
 
     #[test]
     fn test_filters_when_fr_and_english_quotation_marks() {
-        let filter = QuotesFilter { locale: "fr".to_string() };
-
-        let result = filter.check("“Et moi aussi”, dit l’Anglais.");
+        let filter = QuotesFilter { locale: "fr".parse().unwrap(), spacing: FrenchSpacing::Lenient };
 
-        assert!(result.is_err());
-
-        let warnings = result.err().unwrap();
+        let warnings = filter.check("“Et moi aussi”, dit l’Anglais.");
 
         assert_eq!(1, warnings.len());
         assert_eq!("Please use french quotation marks with non-breaking spaces.", warnings[0].message);
@@ -283,13 +280,9 @@ mod tests {
 
     #[test]
     fn test_filters_when_fr_and_french_quotation_marks_without_non_breaking_spaces() {
-        let filter = QuotesFilter { locale: "fr".to_string() };
-
-        let result = filter.check("«Et moi aussi», dit l’Anglais.");
+        let filter = QuotesFilter { locale: "fr".parse().unwrap(), spacing: FrenchSpacing::Lenient };
 
-        assert!(result.is_err());
-
-        let warnings = result.err().unwrap();
+        let warnings = filter.check("«Et moi aussi», dit l’Anglais.");
 
         assert_eq!(1, warnings.len());
         assert_eq!("Please use french quotation marks with non-breaking spaces.", warnings[0].message);
@@ -299,23 +292,18 @@ mod tests {
 
     #[test]
     fn test_filter_when_fr_and_no_warnings() {
-        let filter = QuotesFilter { locale: "fr".to_string() };
+        let filter = QuotesFilter { locale: "fr".parse().unwrap(), spacing: FrenchSpacing::Lenient };
 
         let result = filter.check("« Et moi aussi », dit l’Anglais.");
 
-        assert_eq!(false, result.is_err());
-        assert_eq!((), result.unwrap());
+        assert!(result.is_empty());
     }
 
     #[test]
     fn test_filters_when_it_and_straight_quotation_marks() {
-        let filter = QuotesFilter { locale: "it".to_string() };
-
-        let result = filter.check("\"Anche a me\", ha detto la spagnola");
+        let filter = QuotesFilter { locale: "it".parse().unwrap(), spacing: FrenchSpacing::Lenient };
 
-        assert!(result.is_err());
-
-        let warnings = result.err().unwrap();
+        let warnings = filter.check("\"Anche a me\", ha detto la spagnola");
 
         assert_eq!(1, warnings.len());
         assert_eq!("Please use french quotation marks without spaces.", warnings[0].message);
@@ -325,13 +313,9 @@ mod tests {
 
     #[test]
     fn test_filters_when_it_and_english_quotation_marks() {
-        let filter = QuotesFilter { locale: "it".to_string() };
-
-        let result = filter.check("“Anche a me”, ha detto la spagnola");
+        let filter = QuotesFilter { locale: "it".parse().unwrap(), spacing: FrenchSpacing::Lenient };
 
-        assert!(result.is_err());
-
-        let warnings = result.err().unwrap();
+        let warnings = filter.check("“Anche a me”, ha detto la spagnola");
 
         assert_eq!(1, warnings.len());
         assert_eq!("Please use french quotation marks without spaces.", warnings[0].message);
@@ -341,13 +325,9 @@ mod tests {
 
     #[test]
     fn test_filters_when_it_and_french_quotation_marks_with_spaces() {
-        let filter = QuotesFilter { locale: "it".to_string() };
-
-        let result = filter.check("« Anche a me », ha detto la spagnola");
+        let filter = QuotesFilter { locale: "it".parse().unwrap(), spacing: FrenchSpacing::Lenient };
 
-        assert!(result.is_err());
-
-        let warnings = result.err().unwrap();
+        let warnings = filter.check("« Anche a me », ha detto la spagnola");
 
         assert_eq!(1, warnings.len());
         assert_eq!("Please use french quotation marks without spaces.", warnings[0].message);
@@ -357,12 +337,82 @@ mod tests {
 
     #[test]
     fn test_filter_when_it_and_no_warnings() {
-        let filter = QuotesFilter { locale: "it".to_string() };
+        let filter = QuotesFilter { locale: "it".parse().unwrap(), spacing: FrenchSpacing::Lenient };
 
         let result = filter.check("«Anche a me», ha detto la spagnola");
 
-        assert_eq!(false, result.is_err());
-        assert_eq!((), result.unwrap());
+        assert!(result.is_empty());
     }
-}
 
+    #[test]
+    fn test_fixes_when_en_and_straight_quotation_marks() {
+        let filter = QuotesFilter { locale: "en".parse().unwrap(), spacing: FrenchSpacing::Lenient };
+
+        let warnings = filter.check("\"Mee too\", said the French.");
+
+        assert_eq!(
+            vec![
+                LinterFix { start: 0, end: 1, replacement: "“".to_string() },
+                LinterFix { start: 8, end: 9, replacement: "”".to_string() },
+            ],
+            warnings[0].fixes
+        );
+    }
+
+    #[test]
+    fn test_fixes_when_fr_and_english_quotation_marks_with_spaces() {
+        let filter = QuotesFilter { locale: "fr".parse().unwrap(), spacing: FrenchSpacing::Lenient };
+
+        let warnings = filter.check("“ Et moi aussi ”, dit l’Anglais.");
+
+        assert_eq!(
+            vec![
+                LinterFix { start: 0, end: 4, replacement: "«\u{a0}".to_string() },
+                LinterFix { start: 16, end: 20, replacement: "\u{a0}»".to_string() },
+            ],
+            warnings[0].fixes
+        );
+    }
+
+    #[test]
+    fn test_filters_when_fr_and_spacing_policies() {
+        let text = "«\u{202f}Et moi aussi\u{202f}», dit l’Anglais.";
+
+        let lenient = QuotesFilter { locale: "fr".parse().unwrap(), spacing: FrenchSpacing::Lenient };
+        let strict = QuotesFilter { locale: "fr".parse().unwrap(), spacing: FrenchSpacing::Strict };
+
+        assert!(lenient.check(text).is_empty());
+        assert_eq!(
+            vec![
+                LinterFix { start: 0, end: 5, replacement: "«\u{a0}".to_string() },
+                LinterFix { start: 17, end: 22, replacement: "\u{a0}»".to_string() },
+            ],
+            strict.check(text)[0].fixes
+        );
+    }
+
+    #[test]
+    fn test_fixes_when_two_quotations_in_a_sentence() {
+        let filter = QuotesFilter { locale: "en".parse().unwrap(), spacing: FrenchSpacing::Lenient };
+        let text = "He said \"yes\" and she said \"no\".";
+
+        let warnings = filter.check(text);
+
+        assert_eq!(2, warnings.len());
+        assert_eq!((8, 13), (warnings[0].start, warnings[0].end));
+        assert_eq!((27, 31), (warnings[1].start, warnings[1].end));
+        assert_eq!("He said “yes” and she said “no”.", fixes::apply_fixes(text, &warnings).text);
+    }
+
+    #[test]
+    fn test_fixes_when_a_quotation_wraps_onto_another_line() {
+        let filter = QuotesFilter { locale: "en".parse().unwrap(), spacing: FrenchSpacing::Lenient };
+        let text = "He said \"yes,\nof course\".";
+
+        let warnings = filter.check(text);
+
+        assert_eq!(1, warnings.len());
+        assert_eq!((8, 24), (warnings[0].start, warnings[0].end));
+        assert_eq!("He said “yes,\nof course”.", fixes::apply_fixes(text, &warnings).text);
+    }
+}
@@ -1,18 +1,52 @@
 use super::*;
 
-pub struct SpaceBeforeDoublePonctuationFilter {}
+pub struct SpaceBeforeDoublePonctuationFilter {
+    pub spacing: FrenchSpacing,
+}
 
 impl LinterFilter for SpaceBeforeDoublePonctuationFilter {
+    fn fixes(&self, text: &str, start: usize, end: usize) -> Vec<LinterFix> {
+        // The ponctuation mark is always the last (single byte) character of the match.
+        let mark = end - 1;
+        let replacement = self.spacing.space(text[mark..].chars().next().unwrap()).to_string();
+
+        match text[start..mark].chars().last() {
+            Some(space) if space.is_whitespace() => {
+                vec![LinterFix { start: mark - space.len_utf8(), end: mark, replacement }]
+            }
+            _ => vec![LinterFix { start: mark, end: mark, replacement }],
+        }
+    }
+
+    fn id(&self) -> &'static str {
+        "space-before-double-punctuation"
+    }
+
     fn locales(&self) -> Vec<&'static str> {
         vec!["fr"]
     }
 
     fn message(&self) -> &'static str {
-        "Please use a non-breaking space before “double” ponctuation marks: `;`, `:`, `!`, `?`."
+        match self.spacing {
+            FrenchSpacing::Strict => {
+                "Please use a narrow non-breaking space before `;`, `!`, `?` and a non-breaking space before `:`."
+            }
+            FrenchSpacing::Lenient => {
+                "Please use a non-breaking space before “double” ponctuation marks: `;`, `:`, `!`, `?`."
+            }
+            FrenchSpacing::WebSafe => {
+                "Please use a non-breaking space (U+00A0) before “double” ponctuation marks: `;`, `:`, `!`, `?`."
+            }
+        }
     }
 
     fn regex_pattern(&self) -> String {
-        r"[\w ][;:!?]".to_string()
+        // Matches a word character, a space or a rejected non-breaking space before a mark.
+        format!(
+            r"[\w {}][;!?]|[\w {}]:",
+            self.spacing.rejected_spaces('!'),
+            self.spacing.rejected_spaces(':')
+        )
     }
 }
 
@@ -22,23 +56,18 @@ mod tests {
 
     #[test]
     fn test_filter_with_no_warnings() {
-        let filter = SpaceBeforeDoublePonctuationFilter {};
+        let filter = SpaceBeforeDoublePonctuationFilter { spacing: FrenchSpacing::Lenient };
 
         let result = filter.check("Ah ! Non ! C’est un peu court, jeune homme !");
 
-        assert_eq!(false, result.is_err());
-        assert_eq!((), result.unwrap());
+        assert!(result.is_empty());
     }
 
     #[test]
     fn test_filter_with_two_warnings() {
-        let filter = SpaceBeforeDoublePonctuationFilter {};
+        let filter = SpaceBeforeDoublePonctuationFilter { spacing: FrenchSpacing::Lenient };
 
-        let result = filter.check("Ah! Non! C’est un peu court, jeune homme !");
-
-        assert!(result.is_err());
-
-        let warnings = result.err().unwrap();
+        let warnings = filter.check("Ah! Non! C’est un peu court, jeune homme !");
 
         assert_eq!(2, warnings.len());
 
@@ -59,14 +88,10 @@ mod tests {
 
     #[test]
     fn test_filter_with_a_missing_non_breaking_space() {
-        let filter = SpaceBeforeDoublePonctuationFilter {};
+        let filter = SpaceBeforeDoublePonctuationFilter { spacing: FrenchSpacing::Lenient };
 
         // The space before the last `!` is not a non-breaking space
-        let result = filter.check("Ah ! Non ! C’est un peu court, jeune homme !");
-
-        assert!(result.is_err());
-
-        let warnings = result.err().unwrap();
+        let warnings = filter.check("Ah ! Non ! C’est un peu court, jeune homme !");
 
         assert_eq!(1, warnings.len());
         assert_eq!(
@@ -76,5 +101,55 @@ mod tests {
         assert_eq!(46, warnings[0].start);
         assert_eq!(48, warnings[0].end);
     }
-}
 
+    #[test]
+    fn test_filter_fixes() {
+        let filter = SpaceBeforeDoublePonctuationFilter { spacing: FrenchSpacing::Lenient };
+
+        let warnings = filter.check("Ah! Non !");
+
+        assert_eq!(2, warnings.len());
+        assert_eq!(
+            vec![LinterFix { start: 2, end: 2, replacement: "\u{a0}".to_string() }],
+            warnings[0].fixes
+        );
+        assert_eq!(
+            vec![LinterFix { start: 7, end: 8, replacement: "\u{a0}".to_string() }],
+            warnings[1].fixes
+        );
+    }
+
+    #[test]
+    fn test_filter_with_strict_spacing() {
+        let filter = SpaceBeforeDoublePonctuationFilter { spacing: FrenchSpacing::Strict };
+
+        let warnings = filter.check("Ah\u{a0}! Non\u{202f}! Voici\u{202f}: oui ;");
+
+        assert_eq!(3, warnings.len());
+        assert_eq!(
+            vec![LinterFix { start: 2, end: 4, replacement: "\u{202f}".to_string() }],
+            warnings[0].fixes
+        );
+        assert_eq!(
+            vec![LinterFix { start: 19, end: 22, replacement: "\u{a0}".to_string() }],
+            warnings[1].fixes
+        );
+        assert_eq!(
+            vec![LinterFix { start: 27, end: 28, replacement: "\u{202f}".to_string() }],
+            warnings[2].fixes
+        );
+    }
+
+    #[test]
+    fn test_filter_with_web_safe_spacing() {
+        let filter = SpaceBeforeDoublePonctuationFilter { spacing: FrenchSpacing::WebSafe };
+
+        let warnings = filter.check("Ah\u{a0}! Non\u{202f}!");
+
+        assert_eq!(1, warnings.len());
+        assert_eq!(
+            vec![LinterFix { start: 9, end: 12, replacement: "\u{a0}".to_string() }],
+            warnings[0].fixes
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use super::*;
+
+use number::NUMBER;
+
+/// Units written after a number: percentages, SI units and common others. Single capital letters
+/// (ex: `A` or `V`) are left out, as they rather label things after numbers (ex: `Plan 2 A`).
+const UNITS: &[&str] = &[
+    // Percentages
+    "%", "‰",
+    // Temperatures
+    "°C", "°F",
+    // Lengths, areas and volumes
+    "nm", "µm", "mm", "cm", "dm", "m", "km", "mm²", "cm²", "m²", "km²", "ha", "cm³", "m³", "ml", "mL", "cl", "cL",
+    "dl", "dL", "l",
+    // Masses
+    "mg", "g", "kg", "t",
+    // Durations and frequencies
+    "ms", "s", "min", "h", "Hz", "kHz", "MHz", "GHz",
+    // Speeds
+    "km/h", "m/s",
+    // Data
+    "bit", "kbit", "Mbit", "Gbit", "kbit/s", "Mbit/s", "Gbit/s", "kB", "MB", "GB", "TB",
+    // Electricity, energy and pressure
+    "mA", "mV", "kV", "kW", "MW", "Wh", "kWh", "MWh", "kJ", "kcal", "Ω", "kΩ", "Pa", "hPa", "kPa", "bar",
+];
+
+pub struct UnitFilter {
+    pub locale: Locale,
+    pub spacing: FrenchSpacing,
+}
+
+impl UnitFilter {
+    /// Returns whether the locale puts a space before `%` (ex: `50 %`).
+    fn has_spaced_percent(&self) -> bool {
+        matches!(self.locale.language(), Language::De | Language::Es | Language::Fr)
+    }
+
+    /// Returns the space expected between a number and its unit, and the accepted ones.
+    fn spaces(&self) -> (char, String) {
+        match self.locale.language() {
+            Language::Fr => (self.spacing.space('%'), self.spacing.accepted_spaces('%')),
+            _ => ('\u{a0}', "\u{a0}\u{202f}".to_string()),
+        }
+    }
+
+    /// Returns the message and fix of a unit and the spaces before it, if they are wrong.
+    fn advice(&self, unit: &str, space: regex::Match) -> Option<(&'static str, LinterFix)> {
+        let (expected, accepted) = self.spaces();
+        let is_percent = unit == "%" || unit == "‰";
+
+        if is_percent && !self.has_spaced_percent() {
+            if space.as_str().is_empty() {
+                return None;
+            }
+
+            return Some((
+                "Please don’t use a space between a number and the percent sign.",
+                LinterFix { start: space.start(), end: space.end(), replacement: String::new() },
+            ));
+        }
+
+        let is_accepted = space.as_str().chars().count() == 1 && accepted.contains(space.as_str());
+        // Single letters right after a number are rather part of it (ex: `the 90s`, `$5m`).
+        let is_ambiguous = space.as_str().is_empty() && unit.len() == 1 && unit.chars().all(char::is_alphabetic);
+
+        if is_accepted || is_ambiguous {
+            return None;
+        }
+
+        let message = if is_percent {
+            "Please use a non-breaking space between a number and the percent sign."
+        } else {
+            "Please use a non-breaking space between a number and its unit."
+        };
+
+        Some((message, LinterFix { start: space.start(), end: space.end(), replacement: expected.to_string() }))
+    }
+}
+
+impl LinterFilter for UnitFilter {
+    fn check_with_regex(&self, regex: &Regex, text: &str) -> Vec<LinterWarning> {
+        regex
+            .captures_iter(text)
+            .filter_map(|captures| {
+                let whole = captures.get(0).unwrap();
+                let unit = &captures["unit"];
+
+                // Digits within identifiers aren’t numbers (ex: `x2 m`).
+                let previous = text[..whole.start()].chars().next_back();
+
+                if previous.is_some_and(|previous| previous.is_alphanumeric() || previous == '_') {
+                    return None;
+                }
+
+                // Words starting like a unit aren’t units (ex: `5 minutes`), nor are single letters
+                // joined to a word (ex: `3 t-shirts` or `1 l’eau`).
+                let next = text[whole.end()..].chars().next();
+                let is_letter = unit.chars().count() == 1 && unit.chars().all(char::is_alphabetic);
+
+                if next.is_some_and(|next| next.is_alphanumeric() || (is_letter && "-'’".contains(next))) {
+                    return None;
+                }
+
+                let (message, fix) = self.advice(unit, captures.name("space").unwrap())?;
+
+                Some(LinterWarning {
+                    rule: self.id().to_string(),
+                    severity: self.severity(),
+                    message: message.to_string(),
+                    start: whole.start(),
+                    end: whole.end(),
+                    fixes: vec![fix],
+                    ..Default::default()
+                })
+            })
+            .collect()
+    }
+
+    fn id(&self) -> &'static str {
+        "unit"
+    }
+
+    fn message(&self) -> &'static str {
+        "Please use a non-breaking space between a number and its unit."
+    }
+
+    fn regex_pattern(&self) -> String {
+        let mut units = UNITS.to_vec();
+
+        // Longer units first, for `mm` not to be matched as `m`.
+        units.sort_by_key(|unit| std::cmp::Reverse(unit.len()));
+
+        format!(
+            r"(?:{})(?P<space>[ \u{{a0}}\u{{202f}}]*)(?P<unit>{})",
+            NUMBER,
+            units.iter().map(|unit| regex::escape(unit)).collect::<Vec<String>>().join("|")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(locale: &str) -> UnitFilter {
+        UnitFilter { locale: locale.parse().unwrap(), spacing: FrenchSpacing::Lenient }
+    }
+
+    fn fix(filter: &UnitFilter, text: &str) -> String {
+        fixes::apply_fixes(text, &filter.check(text)).text
+    }
+
+    #[test]
+    fn test_unit_filter_with_english() {
+        let filter = filter("en");
+
+        let warnings = filter.check("It’s 50 % off, 20 °C and 10km away.");
+
+        assert_eq!(3, warnings.len());
+        assert_eq!("Please don’t use a space between a number and the percent sign.", warnings[0].message);
+        assert_eq!((7, 11), (warnings[0].start, warnings[0].end));
+        assert_eq!("Please use a non-breaking space between a number and its unit.", warnings[1].message);
+        assert_eq!(
+            "It’s 50% off, 20\u{a0}°C and 10\u{a0}km away.",
+            fix(&filter, "It’s 50 % off, 20 °C and 10km away.")
+        );
+    }
+
+    #[test]
+    fn test_unit_filter_with_french() {
+        let filter = filter("fr");
+
+        assert_eq!(
+            "50\u{a0}% de 1,5\u{a0}kg en 5\u{a0}min",
+            fix(&filter, "50% de 1,5 kg en 5  min")
+        );
+        assert!(filter.check("50\u{a0}% de 1,5\u{202f}kg en 5\u{a0}minutes, 2\u{a0}h").is_empty());
+    }
+
+    #[test]
+    fn test_unit_filter_with_no_warnings() {
+        let filter = filter("de");
+
+        assert!(filter.check("Die 90s, 5 Minuten, 4K und 3 Mal.").is_empty());
+    }
+
+    #[test]
+    fn test_unit_filter_with_words_and_identifiers() {
+        assert!(filter("en").check("3 t-shirts, Plan 2 A and x2 m.").is_empty());
+        assert!(filter("fr").check("1 l'eau, 1 l’eau et 3 t-shirts.").is_empty());
+        assert_eq!("Il fait 2\u{a0}m de haut.", fix(&filter("fr"), "Il fait 2 m de haut."));
+    }
+}
@@ -0,0 +1,140 @@
+use crate::errors::{LinterFix, LinterWarning};
+
+#[derive(Debug, Serialize)]
+pub struct FixResult {
+    pub text: String,
+    pub applied: Vec<LinterFix>,
+}
+
+/// Applies the fixes of the given warnings to `text` in one pass.
+///
+/// The fixes of a warning are applied all together or not at all: when one of them overlaps a
+/// fix that has already been accepted, the whole warning is left unfixed. So is a warning with an
+/// invalid fix (out of the text, not on character boundaries or overlapping another of its fixes),
+/// which custom filters could return.
+pub fn apply_fixes(text: &str, warnings: &[LinterWarning]) -> FixResult {
+    let mut candidates: Vec<&LinterWarning> = warnings
+        .iter()
+        .filter(|warning| !warning.fixes.is_empty())
+        .collect();
+
+    candidates.sort_by_key(|warning| {
+        warning.fixes.iter().map(|fix| fix.start).min().unwrap_or(0)
+    });
+
+    let mut applied = Vec::<LinterFix>::new();
+
+    for warning in candidates {
+        let is_valid = warning.fixes.iter().enumerate().all(|(index, fix)| {
+            fix.start <= fix.end
+                && text.is_char_boundary(fix.start)
+                && text.is_char_boundary(fix.end)
+                && !warning.fixes[..index].iter().any(|other| overlaps(fix, other))
+        });
+        let conflicts = warning.fixes.iter().any(|fix| applied.iter().any(|other| overlaps(fix, other)));
+
+        if is_valid && !conflicts {
+            applied.extend(warning.fixes.iter().cloned());
+        }
+    }
+
+    applied.sort_by_key(|fix| (fix.start, fix.end));
+
+    let mut fixed = String::with_capacity(text.len());
+    let mut position = 0;
+
+    for fix in &applied {
+        fixed.push_str(&text[position..fix.start]);
+        fixed.push_str(&fix.replacement);
+        position = fix.end;
+    }
+
+    fixed.push_str(&text[position..]);
+
+    FixResult { text: fixed, applied }
+}
+
+fn overlaps(fix: &LinterFix, other: &LinterFix) -> bool {
+    let is_insertion = fix.start == fix.end;
+    let other_is_insertion = other.start == other.end;
+
+    match (is_insertion, other_is_insertion) {
+        // Two insertions at the same position would have no defined order.
+        (true, true) => fix.start == other.start,
+        (true, false) => other.start < fix.start && fix.start < other.end,
+        (false, true) => fix.start < other.start && other.start < fix.end,
+        (false, false) => fix.start < other.end && other.start < fix.end,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn warning(fixes: Vec<LinterFix>) -> LinterWarning {
+        let start = fixes.iter().map(|fix| fix.start).min().unwrap_or(0);
+        let end = fixes.iter().map(|fix| fix.end).max().unwrap_or(0);
+
+        LinterWarning { start, end, fixes, ..Default::default() }
+    }
+
+    fn fix(start: usize, end: usize, replacement: &str) -> LinterFix {
+        LinterFix { start, end, replacement: replacement.to_string() }
+    }
+
+    #[test]
+    fn test_apply_fixes() {
+        let warnings = vec![
+            warning(vec![fix(7, 10, "…")]),
+            warning(vec![fix(2, 3, "’")]),
+        ];
+
+        let result = apply_fixes("It's me...", &warnings);
+
+        assert_eq!("It’s me…", result.text);
+        assert_eq!(vec![fix(2, 3, "’"), fix(7, 10, "…")], result.applied);
+    }
+
+    #[test]
+    fn test_apply_fixes_skips_overlapping_warnings() {
+        let warnings = vec![
+            warning(vec![fix(0, 1, "“"), fix(4, 5, "”")]),
+            warning(vec![fix(3, 5, "\"")]),
+            warning(vec![fix(3, 3, "\u{a0}")]),
+        ];
+
+        let result = apply_fixes("\"Ah \"", &warnings);
+
+        assert_eq!("“Ah\u{a0} ”", result.text);
+        assert_eq!(3, result.applied.len());
+    }
+
+    #[test]
+    fn test_apply_fixes_skips_insertions_at_the_same_position() {
+        let warnings = vec![
+            warning(vec![fix(2, 2, "\u{a0}")]),
+            warning(vec![fix(2, 2, "\u{202f}")]),
+        ];
+
+        let result = apply_fixes("Ah!", &warnings);
+
+        assert_eq!("Ah\u{a0}!", result.text);
+        assert_eq!(vec![fix(2, 2, "\u{a0}")], result.applied);
+    }
+
+    #[test]
+    fn test_apply_fixes_skips_invalid_fixes() {
+        let warnings = vec![
+            warning(vec![fix(2, 1, "’")]),
+            warning(vec![fix(8, 20, "…")]),
+            warning(vec![fix(1, 3, "")]),
+            warning(vec![fix(0, 3, "«"), fix(0, 6, "«\u{a0}")]),
+            warning(vec![fix(5, 6, "")]),
+        ];
+
+        let result = apply_fixes("“Ah ” !", &warnings);
+
+        assert_eq!("“Ah” !", result.text);
+        assert_eq!(vec![fix(5, 6, "")], result.applied);
+    }
+}
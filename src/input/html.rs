@@ -0,0 +1,264 @@
+use super::*;
+
+/// Elements whose content isn’t prose.
+const SKIPPED_ELEMENTS: &[&str] = &["code", "kbd", "pre", "samp", "script", "style", "svg", "template", "textarea", "var"];
+
+/// Skipped elements standing for a word of the prose.
+const INLINE_SKIPPED_ELEMENTS: &[&str] = &["code", "kbd", "samp", "var"];
+
+/// Elements whose content is raw text, which can contain `<` without starting a tag.
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style", "textarea"];
+
+/// Elements separating blocks of prose.
+const BLOCK_ELEMENTS: &[&str] = &[
+    "address", "article", "aside", "blockquote", "body", "caption", "dd", "details", "div", "dl", "dt",
+    "figcaption", "figure", "footer", "form", "h1", "h2", "h3", "h4", "h5", "h6", "head", "header", "hr",
+    "html", "li", "main", "nav", "ol", "p", "section", "summary", "table", "td", "th", "title", "tr", "ul",
+];
+
+/// Extracts the text nodes of an HTML document, with their entities decoded: tags, attributes,
+/// comments and the content of code elements (`<code>`, `<pre>`, `<script>`…) are left out.
+pub fn extract(text: &str) -> ExtractedText {
+    let mut extracted = ExtractedText::default();
+    // Depth of the elements whose content isn’t prose.
+    let mut skipped: usize = 0;
+    let mut position = 0;
+
+    while position < text.len() {
+        let rest = &text[position..];
+
+        if !rest.starts_with('<') {
+            let end = rest.find('<').map_or(text.len(), |offset| position + offset);
+
+            if skipped == 0 {
+                push_text(&mut extracted, position, &text[position..end]);
+            }
+
+            position = end;
+            continue;
+        }
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            position += comment.find("-->").map_or(rest.len(), |offset| offset + 7);
+            continue;
+        }
+
+        let tag = match Tag::parse(rest) {
+            Some(tag) => tag,
+            None => {
+                // A lone `<` is text.
+                if skipped == 0 {
+                    extracted.push_verbatim(position, "<");
+                }
+
+                position += 1;
+                continue;
+            },
+        };
+
+        let tag_start = position;
+
+        position += tag.length;
+
+        if tag.is_closing {
+            if SKIPPED_ELEMENTS.contains(&tag.name.as_str()) {
+                skipped = skipped.saturating_sub(1);
+            }
+        } else if SKIPPED_ELEMENTS.contains(&tag.name.as_str()) && !tag.is_self_closing {
+            if skipped == 0 && INLINE_SKIPPED_ELEMENTS.contains(&tag.name.as_str()) {
+                extracted.push_placeholder(tag_start, position);
+            } else if skipped == 0 {
+                extracted.push_break(tag_start);
+            }
+
+            skipped += 1;
+
+            if RAW_TEXT_ELEMENTS.contains(&tag.name.as_str()) {
+                let closing_tag = format!("</{}", tag.name);
+
+                position = text[position..]
+                    .to_ascii_lowercase()
+                    .find(&closing_tag)
+                    .map_or(text.len(), |offset| position + offset);
+            }
+        }
+
+        if skipped > 0 {
+            continue;
+        }
+
+        if tag.name == "br" {
+            extracted.push_hidden(tag_start, position, "\n");
+        } else if BLOCK_ELEMENTS.contains(&tag.name.as_str()) {
+            extracted.push_break(tag_start);
+        }
+    }
+
+    extracted
+}
+
+/// A start or end tag (ex: `<p class="note">`, `</p>`, `<br/>`).
+struct Tag {
+    name: String,
+    is_closing: bool,
+    is_self_closing: bool,
+    /// Length of the tag in bytes, attributes included.
+    length: usize,
+}
+
+impl Tag {
+    fn parse(source: &str) -> Option<Tag> {
+        let (is_closing, after_bracket) = match source.strip_prefix("</") {
+            Some(rest) => (true, rest),
+            None => (false, source.strip_prefix('<')?),
+        };
+
+        // Declarations (`<!DOCTYPE html>`) and processing instructions are skipped like tags.
+        let is_declaration = !is_closing && (after_bracket.starts_with('!') || after_bracket.starts_with('?'));
+
+        if !is_declaration && !after_bracket.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            return None;
+        }
+
+        let name_length = after_bracket
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '!' || c == '?'))
+            .unwrap_or(after_bracket.len());
+
+        // Attribute values can contain `>`.
+        let mut quote = None;
+        let mut end = None;
+
+        for (offset, c) in after_bracket.char_indices().skip(name_length) {
+            match (quote, c) {
+                (Some(opening), _) if c == opening => quote = None,
+                (Some(_), _) => {},
+                (None, '"') | (None, '\'') => quote = Some(c),
+                (None, '>') => {
+                    end = Some(offset);
+                    break;
+                },
+                _ => {},
+            }
+        }
+
+        let end = end?;
+
+        Some(Tag {
+            name: after_bracket[..name_length].to_lowercase(),
+            is_closing,
+            is_self_closing: after_bracket[..end].ends_with('/'),
+            length: source.len() - after_bracket.len() + end + 1,
+        })
+    }
+}
+
+/// Appends a text node to the prose, decoding its character references.
+fn push_text(extracted: &mut ExtractedText, source_start: usize, text: &str) {
+    let mut position = 0;
+
+    for (offset, _) in text.match_indices('&') {
+        if offset < position {
+            continue;
+        }
+
+        let reference = &text[offset..];
+
+        let decoded = reference
+            .find(';')
+            .filter(|end| *end <= 32)
+            .and_then(|end| decode_reference(&reference[1..end]).map(|decoded| (decoded, end + 1)));
+
+        if let Some((decoded, length)) = decoded {
+            extracted.push_verbatim(source_start + position, &text[position..offset]);
+            extracted.push_replaced(source_start + offset, source_start + offset + length, &decoded.to_string());
+            position = offset + length;
+        }
+    }
+
+    extracted.push_verbatim(source_start + position, &text[position..]);
+}
+
+/// Decodes a numeric or named character reference, without its `&` and `;`.
+pub(super) fn decode_reference(reference: &str) -> Option<char> {
+    if let Some(number) = reference.strip_prefix('#') {
+        let code = match number.strip_prefix(['x', 'X']) {
+            Some(hexadecimal) => u32::from_str_radix(hexadecimal, 16).ok()?,
+            None => number.parse().ok()?,
+        };
+
+        return char::from_u32(code);
+    }
+
+    let decoded = match reference {
+        "amp" => '&',
+        "apos" => '\'',
+        "bdquo" => '„',
+        "copy" => '©',
+        "emsp" => '\u{2003}',
+        "ensp" => '\u{2002}',
+        "euro" => '€',
+        "gt" => '>',
+        "hellip" => '…',
+        "laquo" => '«',
+        "ldquo" => '“',
+        "lsaquo" => '‹',
+        "lsquo" => '‘',
+        "lt" => '<',
+        "mdash" => '—',
+        "nbsp" => '\u{a0}',
+        "ndash" => '–',
+        "pound" => '£',
+        "quot" => '"',
+        "raquo" => '»',
+        "rdquo" => '”',
+        "reg" => '®',
+        "rsaquo" => '›',
+        "rsquo" => '’',
+        "sbquo" => '‚',
+        "shy" => '\u{ad}',
+        "thinsp" => '\u{2009}',
+        "trade" => '™',
+        "yen" => '¥',
+        _ => return None,
+    };
+
+    Some(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_skips_markup() {
+        let extracted = extract(
+            "<!DOCTYPE html><p class=\"note\" data-x='a>b'>It's <em>me</em>...<!-- \"x\" --></p>\
+             <pre>let s = \"...\";</pre><script>if (a < b) { s = \"...\"; }</script><p>Done.</p>",
+        );
+
+        assert_eq!("It's me...\n\nDone.", extracted.text.trim_end());
+    }
+
+    #[test]
+    fn test_extract_decodes_entities() {
+        let source = "<p>Oui&nbsp;! Non&#8239;? &laquo;&#x20AC;&raquo; &unknown; R&amp;D...</p>";
+        let extracted = extract(source);
+
+        assert_eq!("Oui\u{a0}! Non\u{202f}? «€» &unknown; R&D...", extracted.text.trim_end());
+
+        let (start, end) = extracted.source_range(3, 5);
+
+        assert_eq!("&nbsp;", &source[start..end]);
+
+        let (start, end) = extracted.source_range(36, 39);
+
+        assert_eq!("...", &source[start..end]);
+    }
+
+    #[test]
+    fn test_extract_with_nested_code() {
+        let extracted = extract("<p>Use <code>it's <b>x</b>...</code>, it's fine.<br/>Ok</p>");
+
+        assert_eq!("Use \u{fffc}, it's fine.\nOk", extracted.text.trim_end());
+    }
+}
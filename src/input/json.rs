@@ -0,0 +1,256 @@
+use std::path::Path;
+use std::sync::LazyLock;
+
+use super::message::{escape_with_backslashes, Markup, MessageScanner};
+use super::*;
+
+/// Locales in file or directory names (ex: `fr`, `fr-CA`, `es_419`).
+static LOCALE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[a-zA-Z]{2}(?:[-_](?:[a-zA-Z]{2}|\d{3}))?$").unwrap());
+
+/// Extracts the string values of a JSON message catalogue, each of them being an entry keyed by
+/// its path (ex: `home.title`, `errors.0`). Nested objects and arrays are walked into.
+pub fn extract(text: &str) -> ExtractedText {
+    let mut parser = Parser {
+        source: text,
+        position: 0,
+        scanner: MessageScanner::new(json_escape),
+        extracted: ExtractedText::with_encoder(escape_with_backslashes),
+        path: Vec::new(),
+    };
+
+    parser.value();
+    parser.extracted
+}
+
+/// Returns the locale of a catalogue from its file name (ex: `fr.json`, `messages.fr-CA.json`) or,
+/// failing that, from its directory (ex: `locales/fr/common.json`).
+pub fn locale(path: &Path) -> Option<String> {
+    let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default();
+    let directory = path
+        .parent()
+        .and_then(|directory| directory.file_name())
+        .and_then(|directory| directory.to_str())
+        .unwrap_or_default();
+
+    stem.rsplit('.')
+        .chain(std::iter::once(directory))
+        .find(|candidate| LOCALE_REGEX.is_match(candidate))
+        .map(str::to_string)
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    position: usize,
+    scanner: MessageScanner,
+    extracted: ExtractedText,
+    /// Keys of the objects and indexes of the arrays the parser is in.
+    path: Vec<String>,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        let rest = &self.source[self.position..];
+
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn next_char(&self) -> Option<char> {
+        self.source[self.position..].chars().next()
+    }
+
+    /// Parses a value, stopping at the first syntax error.
+    fn value(&mut self) -> Option<()> {
+        self.skip_whitespace();
+
+        match self.next_char()? {
+            '{' => {
+                self.position += 1;
+
+                loop {
+                    self.skip_whitespace();
+
+                    if self.next_char()? == '}' {
+                        break;
+                    }
+
+                    let (start, end) = self.string()?;
+                    let key = unescape(&self.source[start..end]);
+
+                    self.skip_whitespace();
+
+                    if self.next_char()? != ':' {
+                        return None;
+                    }
+
+                    self.position += 1;
+                    self.path.push(key);
+                    self.value()?;
+                    self.path.pop();
+                    self.skip_whitespace();
+
+                    if self.next_char()? == ',' {
+                        self.position += 1;
+                    }
+                }
+
+                self.position += 1;
+            },
+            '[' => {
+                self.position += 1;
+
+                for index in 0.. {
+                    self.skip_whitespace();
+
+                    if self.next_char()? == ']' {
+                        break;
+                    }
+
+                    self.path.push(index.to_string());
+                    self.value()?;
+                    self.path.pop();
+                    self.skip_whitespace();
+
+                    if self.next_char()? == ',' {
+                        self.position += 1;
+                    }
+                }
+
+                self.position += 1;
+            },
+            '"' => {
+                let (start, end) = self.string()?;
+
+                self.extracted.push_entry(start, &self.path.join("."));
+                self.scanner.push(&mut self.extracted, start, &self.source[start..end]);
+            },
+            _ => {
+                // Numbers, booleans and `null`.
+                let rest = &self.source[self.position..];
+
+                self.position += rest
+                    .find(|c: char| c == ',' || c == '}' || c == ']' || c.is_whitespace())
+                    .unwrap_or(rest.len());
+            },
+        }
+
+        Some(())
+    }
+
+    /// Parses a string, returning the range of its content.
+    fn string(&mut self) -> Option<(usize, usize)> {
+        let start = self.position + 1;
+        let mut escaped = false;
+
+        for (offset, c) in self.source[start..].char_indices() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => {
+                    self.position = start + offset + 1;
+                    return Some((start, start + offset));
+                },
+                _ => {},
+            }
+        }
+
+        None
+    }
+}
+
+/// Recognizes the escape sequences of JSON strings (ex: `\"`, `\u00a0`).
+fn json_escape(source: &str) -> Option<(usize, Markup)> {
+    let escaped = source.strip_prefix('\\')?;
+
+    let (length, decoded) = match escaped.chars().next()? {
+        'u' => {
+            let code = u32::from_str_radix(escaped.get(1..5)?, 16).ok()?;
+
+            // Characters out of the basic multilingual plane are written as surrogate pairs.
+            if (0xd800..0xdc00).contains(&code) {
+                let low = escaped
+                    .get(5..7)
+                    .filter(|prefix| *prefix == "\\u")
+                    .and_then(|_| u32::from_str_radix(escaped.get(7..11)?, 16).ok())?;
+
+                (12, char::from_u32(0x10000 + ((code - 0xd800) << 10) + (low.checked_sub(0xdc00)?))?)
+            } else {
+                (6, char::from_u32(code)?)
+            }
+        },
+        'n' => (2, '\n'),
+        't' => (2, '\t'),
+        'r' => (2, '\r'),
+        'b' => (2, '\u{8}'),
+        'f' => (2, '\u{c}'),
+        escaped => (1 + escaped.len_utf8(), escaped),
+    };
+
+    Some((length, Markup::Decoded(decoded.to_string())))
+}
+
+fn unescape(string: &str) -> String {
+    let mut unescaped = String::with_capacity(string.len());
+    let mut position = 0;
+
+    while let Some(c) = string[position..].chars().next() {
+        match json_escape(&string[position..]) {
+            Some((length, Markup::Decoded(decoded))) => {
+                unescaped.push_str(&decoded);
+                position += length;
+            },
+            _ => {
+                unescaped.push(c);
+                position += c.len_utf8();
+            },
+        }
+    }
+
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CATALOGUE: &str = r#"{
+  "home": {
+    "title": "It's {name}'s home...",
+    "count": "{count, plural, one {# file} other {# \"files\"}}",
+    "enabled": true
+  },
+  "errors": ["Oops !", 12, null]
+}"#;
+
+    #[test]
+    fn test_extract() {
+        let extracted = extract(CATALOGUE);
+
+        assert_eq!(
+            "It's \u{fffc}'s home...\n\n\u{fffc} file\n\n\u{fffc} \"files\"\n\nOops !",
+            extracted.text
+        );
+    }
+
+    #[test]
+    fn test_extract_maps_entries() {
+        let extracted = extract(CATALOGUE);
+
+        let warning = extracted.source_warning(LinterWarning { start: 15, end: 18, ..Default::default() });
+
+        assert_eq!(Some("home.title".to_string()), warning.entry);
+        assert_eq!("...", &CATALOGUE[warning.start..warning.end]);
+
+        let warning = extracted.source_warning(LinterWarning { start: 48, end: 49, ..Default::default() });
+
+        assert_eq!(Some("errors.0".to_string()), warning.entry);
+    }
+
+    #[test]
+    fn test_locale() {
+        assert_eq!(Some("fr".to_string()), locale(Path::new("locales/fr.json")));
+        assert_eq!(Some("fr-CA".to_string()), locale(Path::new("messages.fr-CA.json")));
+        assert_eq!(Some("de".to_string()), locale(Path::new("locales/de/common.json")));
+        assert_eq!(None, locale(Path::new("package.json")));
+    }
+}
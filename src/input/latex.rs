@@ -0,0 +1,386 @@
+use super::*;
+
+/// Environments whose content isn’t prose.
+const SKIPPED_ENVIRONMENTS: &[&str] = &[
+    "align", "align*", "alltt", "comment", "displaymath", "eqnarray", "eqnarray*", "equation", "equation*",
+    "gather", "gather*", "lstlisting", "math", "minted", "multline", "multline*", "tikzpicture", "verbatim",
+    "verbatim*", "Verbatim",
+];
+
+/// Commands standing for a word of the prose, whose arguments aren’t prose.
+const REFERENCE_COMMANDS: &[&str] = &[
+    "autocite", "autoref", "cite", "citep", "citet", "Cref", "cref", "eqref", "nameref", "pageref", "parencite",
+    "ref", "textcite", "url",
+];
+
+/// Commands whose arguments aren’t prose.
+const SETUP_COMMANDS: &[&str] = &[
+    "addtolength", "bibliography", "bibliographystyle", "color", "definecolor", "documentclass", "geometry",
+    "hspace", "hypersetup", "include", "includegraphics", "input", "label", "lstset", "newcommand",
+    "newenvironment", "pagestyle", "providecommand", "renewcommand", "RequirePackage", "selectlanguage",
+    "setcounter", "setlength", "thispagestyle", "usepackage", "vspace",
+];
+
+/// Commands separating blocks of prose.
+const BLOCK_COMMANDS: &[&str] = &[
+    "caption", "chapter", "footnote", "item", "maketitle", "par", "paragraph", "part", "section", "subparagraph",
+    "subsection", "subsubsection", "title",
+];
+
+/// Extracts the prose of a LaTeX document: commands, comments, math and verbatim environments are
+/// left out, and the TeX typographic conventions (``` ``quotes'' ```, `~`, `\,`, `\ldots`, `\og`,
+/// `--`…) are read as the characters they stand for. Fixes are written back with these
+/// conventions.
+pub fn extract(text: &str) -> ExtractedText {
+    let mut extractor = Extractor { source: text, extracted: ExtractedText::with_encoder(encode), run_start: 0 };
+    let mut position = 0;
+
+    while let Some(c) = text[position..].chars().next() {
+        let rest = &text[position..];
+
+        position = match c {
+            '%' => {
+                // A comment ends with its line break.
+                let end = rest.find('\n').map_or(text.len(), |offset| position + offset + 1);
+
+                extractor.skip(position, end)
+            },
+            '\\' => extractor.command(position),
+            '$' => {
+                let (delimiter, is_display) = if rest.starts_with("$$") { ("$$", true) } else { ("$", false) };
+
+                extractor.math(position, delimiter, delimiter, is_display)
+            },
+            '{' | '}' => extractor.skip(position, position + 1),
+            '&' => extractor.separate(position, position + 1),
+            '~' => extractor.replace(position, position + 1, "\u{a0}"),
+            _ => {
+                let ligature = LIGATURES.iter().find(|(ligature, _)| rest.starts_with(ligature));
+
+                match ligature {
+                    Some((ligature, replacement)) => extractor.replace(position, position + ligature.len(), replacement),
+                    None => position + c.len_utf8(),
+                }
+            },
+        };
+    }
+
+    extractor.flush(text.len());
+    extractor.extracted
+}
+
+/// Character sequences standing for a typographic character, longest first.
+const LIGATURES: &[(&str, &str)] = &[
+    ("---", "—"),
+    ("--", "–"),
+    ("``", "“"),
+    ("''", "”"),
+    (",,", "„"),
+    ("<<", "«"),
+    (">>", "»"),
+    ("`", "‘"),
+    ("'", "’"),
+];
+
+/// Commands standing for a typographic character.
+const SYMBOL_COMMANDS: &[(&str, &str)] = &[
+    ("dots", "…"),
+    ("euro", "€"),
+    ("fg", "\u{a0}»"),
+    ("guillemotleft", "«"),
+    ("guillemotright", "»"),
+    ("ldots", "…"),
+    ("nobreakspace", "\u{a0}"),
+    ("og", "«\u{a0}"),
+    ("textellipsis", "…"),
+    ("texteuro", "€"),
+    ("textquotedblleft", "“"),
+    ("textquotedblright", "”"),
+    ("textquoteleft", "‘"),
+    ("textquoteright", "’"),
+    ("thinspace", "\u{202f}"),
+];
+
+struct Extractor<'a> {
+    source: &'a str,
+    extracted: ExtractedText,
+    /// Start of the prose not yet appended to the extracted text.
+    run_start: usize,
+}
+
+impl Extractor<'_> {
+    /// Appends the prose found before `end`.
+    fn flush(&mut self, end: usize) {
+        if self.run_start < end {
+            self.extracted.push_verbatim(self.run_start, &self.source[self.run_start..end]);
+        }
+    }
+
+    /// Leaves out a range of the source, returning its end.
+    fn skip(&mut self, start: usize, end: usize) -> usize {
+        self.flush(start);
+        self.run_start = end;
+        end
+    }
+
+    fn replace(&mut self, start: usize, end: usize, text: &str) -> usize {
+        self.skip(start, end);
+        self.extracted.push_replaced(start, end, text);
+        end
+    }
+
+    /// Stands for a range of the source that fixes can’t change.
+    fn hide(&mut self, start: usize, end: usize, text: &str) -> usize {
+        self.skip(start, end);
+        self.extracted.push_hidden(start, end, text);
+        end
+    }
+
+    fn separate(&mut self, start: usize, end: usize) -> usize {
+        self.skip(start, end);
+        self.extracted.push_break(start);
+        end
+    }
+
+    fn command(&mut self, start: usize) -> usize {
+        let rest = &self.source[start + 1..];
+
+        let name_length = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+
+        if name_length == 0 {
+            return self.control_symbol(start);
+        }
+
+        let name = &rest[..name_length];
+        let mut end = start + 1 + name_length;
+
+        if self.source[end..].starts_with('*') {
+            end += 1;
+        }
+
+        if let Some((_, replacement)) = SYMBOL_COMMANDS.iter().find(|(command, _)| *command == name) {
+            let mut start = start;
+
+            // `\og` and `\fg` come with their own spaces, in place of the ones typed around them.
+            if name == "og" {
+                end += self.source[end..].len() - self.source[end..].trim_start().len();
+            } else if name == "fg" {
+                start = self.source[..start].trim_end().len().max(self.run_start);
+            }
+
+            if self.source[end..].starts_with("{}") {
+                end += 2;
+            }
+
+            return self.replace(start, end, replacement);
+        }
+
+        match name {
+            "begin" => self.environment(start, end),
+            "end" => {
+                let end = self.skip_arguments(end, Some(1));
+
+                self.separate(start, end)
+            },
+            "verb" | "lstinline" => {
+                let delimiter = self.source[end..].chars().next();
+
+                let verbatim_end = delimiter
+                    .and_then(|delimiter| {
+                        let content_start = end + delimiter.len_utf8();
+
+                        self.source[content_start..]
+                            .find(delimiter)
+                            .map(|offset| content_start + offset + delimiter.len_utf8())
+                    })
+                    .unwrap_or(self.source.len());
+
+                self.hide(start, verbatim_end, PLACEHOLDER)
+            },
+            "href" => {
+                let end = self.skip_arguments(end, Some(1));
+
+                self.skip(start, end)
+            },
+            _ if REFERENCE_COMMANDS.contains(&name) => {
+                let end = self.skip_arguments(end, None);
+
+                self.hide(start, end, PLACEHOLDER)
+            },
+            _ if SETUP_COMMANDS.contains(&name) => {
+                let end = self.skip_arguments(end, None);
+
+                self.skip(start, end)
+            },
+            _ if BLOCK_COMMANDS.contains(&name) => self.separate(start, end),
+            // The arguments of other commands (`\emph{…}`, `\textbf{…}`…) are prose.
+            _ => self.skip(start, end),
+        }
+    }
+
+    /// Handles a command made of a backslash and a symbol (ex: `\,`, `\%`).
+    fn control_symbol(&mut self, start: usize) -> usize {
+        let symbol = match self.source[start + 1..].chars().next() {
+            Some(symbol) => symbol,
+            None => return self.skip(start, start + 1),
+        };
+
+        let end = start + 1 + symbol.len_utf8();
+
+        match symbol {
+            ',' => self.replace(start, end, "\u{202f}"),
+            ' ' => self.replace(start, end, " "),
+            '\\' => self.hide(start, end, "\n"),
+            '%' | '$' | '&' | '#' | '_' | '{' | '}' => self.replace(start, end, &symbol.to_string()),
+            '(' => self.math(start, "\\(", "\\)", false),
+            '[' => self.math(start, "\\[", "\\]", true),
+            // Accents are left out, keeping the accented letter (ex: `\'e`).
+            _ => self.skip(start, end),
+        }
+    }
+
+    /// Handles a formula, which stands for a word of the prose or separates blocks when displayed.
+    fn math(&mut self, start: usize, opening: &str, closing: &str, is_display: bool) -> usize {
+        let content_start = start + opening.len();
+
+        let end = self.source[content_start..]
+            .find(closing)
+            .map_or(self.source.len(), |offset| content_start + offset + closing.len());
+
+        if is_display {
+            self.separate(start, end)
+        } else {
+            self.hide(start, end, PLACEHOLDER)
+        }
+    }
+
+    fn environment(&mut self, start: usize, end: usize) -> usize {
+        let name = self.source[end..]
+            .strip_prefix('{')
+            .and_then(|rest| rest.find('}').map(|length| &rest[..length]))
+            .unwrap_or_default();
+
+        if SKIPPED_ENVIRONMENTS.contains(&name) {
+            let closing = format!("\\end{{{}}}", name);
+
+            let end = self.source[end..]
+                .find(&closing)
+                .map_or(self.source.len(), |offset| end + offset + closing.len());
+
+            return self.separate(start, end);
+        }
+
+        // Column specifications (`\begin{tabular}{ll}`) and options aren’t prose either.
+        let end = self.skip_arguments(end, None);
+
+        self.separate(start, end)
+    }
+
+    /// Returns the end of the arguments (`[…]` or `{…}`) starting at `position`, reading at most
+    /// `count` of them.
+    fn skip_arguments(&self, mut position: usize, count: Option<usize>) -> usize {
+        let mut read = 0;
+
+        while count.is_none_or(|count| read < count) {
+            let closing = match self.source[position..].chars().next() {
+                Some('{') => '}',
+                Some('[') => ']',
+                _ => break,
+            };
+
+            let mut depth = 0;
+            let mut end = None;
+
+            for (offset, c) in self.source[position..].char_indices() {
+                if c == closing && depth == 1 {
+                    end = Some(position + offset + 1);
+                    break;
+                } else if c == '{' || c == '[' {
+                    depth += 1;
+                } else if c == '}' || c == ']' {
+                    depth -= 1;
+                }
+            }
+
+            position = end.unwrap_or(self.source.len());
+            read += 1;
+        }
+
+        position
+    }
+}
+
+/// Writes the replacement of a fix with the TeX conventions.
+fn encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('«', Some('\u{a0}')) => {
+                chars.next();
+                encoded.push_str("\\og ");
+            },
+            ('\u{a0}', Some('»')) => {
+                chars.next();
+                encoded.push_str("\\fg{}");
+            },
+            _ => encode_char(c, &mut encoded),
+        }
+    }
+
+    encoded
+}
+
+fn encode_char(c: char, encoded: &mut String) {
+    match c {
+        '\u{a0}' => encoded.push('~'),
+        '\u{202f}' | '\u{2009}' => encoded.push_str("\\,"),
+        '…' => encoded.push_str("\\ldots{}"),
+        '’' => encoded.push('\''),
+        '‘' => encoded.push('`'),
+        '“' => encoded.push_str("``"),
+        '”' => encoded.push_str("''"),
+        '„' => encoded.push_str(",,"),
+        '«' => encoded.push_str("\\guillemotleft{}"),
+        '»' => encoded.push_str("\\guillemotright{}"),
+        '–' => encoded.push_str("--"),
+        '—' => encoded.push_str("---"),
+        '%' | '$' | '&' | '#' | '_' | '{' | '}' => {
+            encoded.push('\\');
+            encoded.push(c);
+        },
+        _ => encoded.push(c),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_reads_tex_conventions() {
+        let extracted = extract("``It's'' -- see~\\ref{fig:a}\\ldots{} 10\\,\\% \\og Oui \\fg{} % it's\nend");
+
+        assert_eq!("“It’s” – see\u{a0}\u{fffc}… 10\u{202f}% «\u{a0}Oui\u{a0}» end", extracted.text);
+    }
+
+    #[test]
+    fn test_extract_skips_commands_math_and_verbatim() {
+        let extracted = extract(
+            "\\section{Intro}\nAs \\emph{shown} in $a'' = \"b\"$ and \\verb|\"x\"|:\n\
+             \\begin{verbatim}\nlet s = \"...\";\n\\end{verbatim}\n\
+             \\begin{tabular}{ll}\nA & B\\\\\n\\end{tabular}\n\\includegraphics[width=1cm]{it's.png}",
+        );
+
+        assert_eq!("Intro\nAs shown in \u{fffc} and \u{fffc}:\n\n\n\n\nA \n\n B\n\n\n", extracted.text);
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!("\\og Oui\\fg{}", encode("«\u{a0}Oui\u{a0}»"));
+        assert_eq!("10~\\$ \\ldots{} ``a''", encode("10\u{a0}$ … “a”"));
+    }
+}
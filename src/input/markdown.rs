@@ -0,0 +1,150 @@
+use std::sync::LazyLock;
+
+use pulldown_cmark::{Event, LinkType, Options, Parser, Tag, TagEnd};
+
+use super::*;
+
+/// URLs written as plain text, which aren’t prose.
+static URL_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(?:https?|ftp)://[^\s<>]+|\bwww\.[^\s<>]+").unwrap());
+
+/// Extracts the prose of a Markdown document: code spans, code blocks, front matter, HTML and URLs
+/// are left out.
+pub fn extract(text: &str) -> ExtractedText {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_YAML_STYLE_METADATA_BLOCKS
+        | Options::ENABLE_PLUSES_DELIMITED_METADATA_BLOCKS;
+
+    let mut extracted = ExtractedText::default();
+    // Depth of the elements whose text isn’t prose (code blocks, autolinks, front matter).
+    let mut skipped = 0;
+
+    for (event, range) in Parser::new_ext(text, options).into_offset_iter() {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) | Event::Start(Tag::MetadataBlock(_)) => skipped += 1,
+            Event::Start(Tag::Link { link_type: LinkType::Autolink | LinkType::Email, .. }) => {
+                skipped += 1;
+                extracted.push_placeholder(range.start, range.end);
+            },
+            Event::End(TagEnd::CodeBlock) | Event::End(TagEnd::MetadataBlock(_)) => {
+                skipped -= 1;
+                extracted.push_break(range.end);
+            },
+            Event::End(TagEnd::Link) if is_autolink(&text[range.clone()]) => skipped -= 1,
+            Event::End(tag) if !is_inline(tag) => extracted.push_break(range.end),
+            Event::Text(_) if skipped > 0 => {},
+            Event::Text(ref prose) if &text[range.clone()] == prose.as_ref() => {
+                push_prose(&mut extracted, range.start, prose);
+            },
+            Event::Text(ref prose) => extracted.push_replaced(range.start, range.end, prose),
+            Event::Code(_) | Event::InlineMath(_) => {
+                extracted.push_placeholder(range.start, range.end);
+            },
+            Event::SoftBreak | Event::HardBreak => {
+                let source = &text[range.clone()];
+
+                if source.trim().is_empty() {
+                    extracted.push_verbatim(range.start, source);
+                } else {
+                    extracted.push_hidden(range.start, range.end, "\n");
+                }
+            },
+            // Inline tags keep the words around them apart (ex: `a<br>b`), line breaks as such.
+            Event::InlineHtml(_) if is_line_break(&text[range.clone()]) => {
+                extracted.push_hidden(range.start, range.end, "\n");
+            },
+            Event::InlineHtml(_) => extracted.push_placeholder(range.start, range.end),
+            Event::Rule | Event::DisplayMath(_) | Event::Html(_) => extracted.push_break(range.end),
+            _ => {},
+        }
+    }
+
+    extracted
+}
+
+/// Appends a text to the prose, replacing its bare URLs.
+fn push_prose(extracted: &mut ExtractedText, source_start: usize, prose: &str) {
+    let mut position = 0;
+
+    for url in URL_REGEX.find_iter(prose) {
+        extracted.push_verbatim(source_start + position, &prose[position..url.start()]);
+        extracted.push_placeholder(source_start + url.start(), source_start + url.end());
+        position = url.end();
+    }
+
+    extracted.push_verbatim(source_start + position, &prose[position..]);
+}
+
+fn is_autolink(source: &str) -> bool {
+    source.starts_with('<') && source.ends_with('>')
+}
+
+fn is_line_break(source: &str) -> bool {
+    let source = source.to_ascii_lowercase();
+
+    source.strip_prefix("<br").is_some_and(|rest| rest.starts_with(['>', '/', ' ', '\t', '\n']))
+}
+
+fn is_inline(tag: TagEnd) -> bool {
+    matches!(
+        tag,
+        TagEnd::Emphasis
+            | TagEnd::Strong
+            | TagEnd::Strikethrough
+            | TagEnd::Superscript
+            | TagEnd::Subscript
+            | TagEnd::Link
+            | TagEnd::Image
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_skips_code() {
+        let extracted = extract("Run `it's` now...\n\n```rust\nlet s = \"...\";\n```\n\nDone.\n");
+
+        assert_eq!("Run \u{fffc} now...\n\nDone.", extracted.text.trim_end());
+    }
+
+    #[test]
+    fn test_extract_skips_urls_and_html() {
+        let extracted = extract(
+            "See [the \"docs\"](https://example.com/it's), <https://example.com/a'b> or \
+             http://example.com/c'd.\n\n<div class=\"note\">\n\nIt's <span title=\"x\">here</span>.\n",
+        );
+
+        assert_eq!("See the \"docs\", \u{fffc} or \u{fffc}\n\nIt's \u{fffc}here\u{fffc}.", extracted.text.trim_end());
+    }
+
+    #[test]
+    fn test_extract_separates_words_around_inline_html() {
+        let extracted = extract("It's a<br>b, a<br/>b and a<span>b</span>.\n");
+
+        assert_eq!("It's a\nb, a\nb and a\u{fffc}b\u{fffc}.", extracted.text.trim_end());
+    }
+
+    #[test]
+    fn test_extract_maps_offsets() {
+        let source = "> It's *very*\n> nice...";
+        let extracted = extract(source);
+
+        assert_eq!("It's very\nnice...", extracted.text.trim_end());
+
+        let (start, end) = extracted.source_range(14, 17);
+
+        assert_eq!("...", &source[start..end]);
+    }
+
+    #[test]
+    fn test_extract_skips_front_matter() {
+        let extracted = extract("---\ntitle: \"It's\"\n---\n\n# Title\n");
+
+        assert_eq!("Title", extracted.text.trim_end());
+    }
+}
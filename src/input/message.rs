@@ -0,0 +1,185 @@
+use std::sync::LazyLock;
+
+use super::*;
+
+/// `printf` formats (ex: `%s`, `%1$d`, `%(name)s`) and Ruby-like named arguments (ex: `%{name}`).
+static PRINTF_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^%(?:\d+\$)?(?:\([\w.]+\))?[-+#0]*(?:\d+|\*)?(?:\.\d+)?(?:hh|h|ll|l|L|q|j|z|t)?[diouxXeEfFgGaAcsSpn@]|^%\{\w+\}",
+    )
+    .unwrap()
+});
+
+/// Named and ICU simple arguments (ex: `{name}`, `{{name}}`, `{count, number}`).
+static ARGUMENT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\{\{\s*[\w.]+\s*\}\}|^\{\s*[\w.]+\s*(?:,\s*\w+\s*(?:,[^{}]*)?)?\}").unwrap()
+});
+
+/// Start of an ICU `plural` or `select` argument, up to its cases.
+static CHOICE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\{\s*[\w.]+\s*,\s*(?:plural|select|selectordinal)\s*,(?:\s*offset:\s*\d+)?").unwrap()
+});
+
+/// Start of a case of an ICU `plural` or `select` argument (ex: `one {`, `=0 {`).
+static CASE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s*(?:=\d+|[\w-]+)\s*\{").unwrap());
+
+/// Markup found by a catalogue format in the source of a message.
+pub enum Markup {
+    /// An escape sequence or an entity, standing for some text (ex: `\"`, `&amp;`).
+    Decoded(String),
+    /// Markup standing for a word of the message (ex: an XLIFF `<ph>` element).
+    Placeholder,
+    /// Markup around a part of the message (ex: an XLIFF `<g>` element).
+    Transparent,
+    /// Markup between alternative texts (ex: the end of a case of an ICU `plural` argument).
+    Break,
+}
+
+/// Where the message scanner is in an ICU `plural` or `select` argument.
+enum Choice {
+    /// Between the cases of the argument (ex: before `one {…}`).
+    Cases,
+    /// In the message of a case.
+    Case,
+}
+
+/// Appends the messages of a catalogue to the prose, replacing their placeholders: `printf`
+/// formats (`%s`, `%1$d`, `%(name)s`), named arguments (`{name}`, `{{name}}`, `%{name}`) and ICU
+/// arguments (`{count, plural, one {# file} other {# files}}`, whose cases are prose).
+pub struct MessageScanner {
+    /// Recognizes the escape sequences and elements of the catalogue format at the start of the
+    /// given source, returning their length.
+    markup: fn(&str) -> Option<(usize, Markup)>,
+}
+
+impl MessageScanner {
+    pub fn new(markup: fn(&str) -> Option<(usize, Markup)>) -> MessageScanner {
+        MessageScanner { markup }
+    }
+
+    /// Appends a message, found at `source_start` in the source.
+    pub fn push(&self, extracted: &mut ExtractedText, source_start: usize, message: &str) {
+        let mut choices = Vec::<Choice>::new();
+        let mut run_start = 0;
+        let mut position = 0;
+
+        while let Some(c) = message[position..].chars().next() {
+            let rest = &message[position..];
+
+            let found = if let Some(found) = (self.markup)(rest) {
+                Some(found)
+            } else if let Some(Choice::Cases) = choices.last() {
+                if let Some(case) = CASE_REGEX.find(rest) {
+                    choices.push(Choice::Case);
+                    Some((case.end(), Markup::Transparent))
+                } else if rest.trim_start().starts_with('}') {
+                    choices.pop();
+                    Some((rest.len() - rest.trim_start().len() + 1, Markup::Transparent))
+                } else {
+                    // Anything else between cases isn’t prose.
+                    Some((c.len_utf8(), Markup::Transparent))
+                }
+            } else if rest.starts_with("%%") {
+                Some((2, Markup::Decoded("%".to_string())))
+            } else if let Some(printf) = PRINTF_REGEX.find(rest) {
+                Some((printf.end(), Markup::Placeholder))
+            } else if let Some(choice) = CHOICE_REGEX.find(rest) {
+                choices.push(Choice::Cases);
+                Some((choice.end(), Markup::Transparent))
+            } else if let Some(argument) = ARGUMENT_REGEX.find(rest) {
+                Some((argument.end(), Markup::Placeholder))
+            } else if let (Some(Choice::Case), '}') = (choices.last(), c) {
+                choices.pop();
+                Some((1, Markup::Break))
+            } else if let (Some(Choice::Case), '#') = (choices.last(), c) {
+                Some((1, Markup::Placeholder))
+            } else {
+                None
+            };
+
+            let (length, found) = match found {
+                Some(found) => found,
+                None => {
+                    position += c.len_utf8();
+                    continue;
+                },
+            };
+
+            extracted.push_verbatim(source_start + run_start, &message[run_start..position]);
+
+            let (start, end) = (source_start + position, source_start + position + length);
+
+            match found {
+                Markup::Decoded(text) => extracted.push_replaced(start, end, &text),
+                Markup::Placeholder => extracted.push_placeholder(start, end),
+                Markup::Transparent => {},
+                Markup::Break => extracted.push_break(start),
+            }
+
+            position += length;
+            run_start = position;
+        }
+
+        extracted.push_verbatim(source_start + run_start, &message[run_start..]);
+    }
+}
+
+/// Recognizes the backslash escape sequences of C-like string literals (ex: `\"`, `\n`).
+pub fn backslash_escape(source: &str) -> Option<(usize, Markup)> {
+    let escaped = source.strip_prefix('\\')?.chars().next()?;
+
+    let decoded = match escaped {
+        'n' => '\n',
+        't' => '\t',
+        'r' => '\r',
+        _ => escaped,
+    };
+
+    Some((1 + escaped.len_utf8(), Markup::Decoded(decoded.to_string())))
+}
+
+/// Writes a text in a C-like string literal.
+pub fn escape_with_backslashes(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extract(message: &str) -> String {
+        let mut extracted = ExtractedText::default();
+
+        MessageScanner::new(backslash_escape).push(&mut extracted, 0, message);
+
+        extracted.text
+    }
+
+    #[test]
+    fn test_scanner_replaces_placeholders() {
+        assert_eq!("\u{fffc} a \u{fffc}\u{fffc}, \u{fffc} \u{fffc}!", extract("%s a %1$d%(n)s, {name} {{ count }}!"));
+        assert_eq!("50 % of \u{fffc} \u{fffc}", extract("50 %% of %{files} {total, number, integer}"));
+        assert_eq!("Say \"hi\"", extract("Say \\\"hi\\\""));
+    }
+
+    #[test]
+    fn test_scanner_keeps_the_cases_of_icu_arguments() {
+        assert_eq!(
+            "It's no file\n\none file\n\n\u{fffc} files\n\n...",
+            extract("It's {count, plural, =0 {no file} one {one file} other {# files}}...")
+        );
+        assert_eq!("He's\n\nShe's\n\n", extract("{gender, select, male {He's} female {She's}}"));
+    }
+}
@@ -0,0 +1,343 @@
+pub mod html;
+pub mod json;
+pub mod latex;
+pub mod markdown;
+pub mod message;
+pub mod po;
+pub mod xliff;
+
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use super::*;
+
+/// Stands for the markup replaced in the extracted prose (inline code, URLs…), so that the words
+/// around it stay apart without any rule matching it.
+pub const PLACEHOLDER: &str = "\u{fffc}";
+
+/// The format of a linted text: only the prose of structured formats is checked.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputFormat {
+    #[default]
+    Text,
+    Markdown,
+    Html,
+    Latex,
+    /// A gettext PO catalogue.
+    Po,
+    /// An XLIFF 1.2 or 2.0 document.
+    Xliff,
+    /// A JSON message catalogue (ex: i18next, `vue-i18n`).
+    Json,
+}
+
+impl InputFormat {
+    /// Guesses the format of a file from its extension, falling back to plain text.
+    pub fn from_path(path: &Path) -> InputFormat {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default()
+            .to_lowercase();
+
+        match extension.as_str() {
+            "md" | "markdown" | "mdown" | "mkd" => InputFormat::Markdown,
+            "html" | "htm" | "xhtml" => InputFormat::Html,
+            "tex" | "ltx" => InputFormat::Latex,
+            "po" | "pot" => InputFormat::Po,
+            "xlf" | "xliff" => InputFormat::Xliff,
+            "json" => InputFormat::Json,
+            _ => InputFormat::Text,
+        }
+    }
+
+    /// Extracts the prose of `text`, or returns `None` when all of it is prose.
+    pub fn extract(&self, text: &str) -> Option<ExtractedText> {
+        match *self {
+            InputFormat::Text => None,
+            InputFormat::Markdown => Some(markdown::extract(text)),
+            InputFormat::Html => Some(html::extract(text)),
+            InputFormat::Latex => Some(latex::extract(text)),
+            InputFormat::Po => Some(po::extract(text)),
+            InputFormat::Xliff => Some(xliff::extract(text)),
+            InputFormat::Json => Some(json::extract(text)),
+        }
+    }
+
+    /// Returns the locale a catalogue is translated in, as declared in `text` or, for JSON
+    /// catalogues, guessed from their `path`.
+    pub fn locale(&self, text: &str, path: Option<&Path>) -> Option<String> {
+        match *self {
+            InputFormat::Po => po::locale(text),
+            InputFormat::Xliff => xliff::locale(text),
+            InputFormat::Json => path.and_then(json::locale),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for InputFormat {
+    type Err = LinterError;
+
+    fn from_str(format: &str) -> Result<InputFormat, LinterError> {
+        match format.to_lowercase().as_str() {
+            "text" | "txt" => Ok(InputFormat::Text),
+            "markdown" | "md" => Ok(InputFormat::Markdown),
+            "html" | "htm" => Ok(InputFormat::Html),
+            "latex" | "tex" => Ok(InputFormat::Latex),
+            "po" => Ok(InputFormat::Po),
+            "xliff" | "xlf" => Ok(InputFormat::Xliff),
+            "json" => Ok(InputFormat::Json),
+            _ => Err(LinterError::UnknownInputFormat(format.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for InputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            InputFormat::Text => "text",
+            InputFormat::Markdown => "markdown",
+            InputFormat::Html => "html",
+            InputFormat::Latex => "latex",
+            InputFormat::Po => "po",
+            InputFormat::Xliff => "xliff",
+            InputFormat::Json => "json",
+        })
+    }
+}
+
+/// How a part of the extracted prose relates to its source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SegmentKind {
+    /// A copy of the source: offsets are mapped one by one, and fixes applied to the source.
+    Verbatim,
+    /// The prose standing for the source (ex: a decoded entity), which fixes can replace as a
+    /// whole.
+    Replaced,
+    /// Markup that fixes must leave untouched (ex: inline code, or a break between blocks).
+    Hidden,
+}
+
+/// A part of the extracted prose, and the range of the source it comes from.
+#[derive(Debug)]
+struct Segment {
+    start: usize,
+    end: usize,
+    source_start: usize,
+    source_end: usize,
+    kind: SegmentKind,
+}
+
+/// The prose of a document, with a map of its segments back to the source.
+#[derive(Debug, Default)]
+pub struct ExtractedText {
+    pub text: String,
+    segments: Vec<Segment>,
+    /// Keys of the catalogue entries, by offset of the prose they start at.
+    entries: Vec<(usize, String)>,
+    /// Writes the replacement of fixes in the syntax of the source (ex: `~` for a non-breaking
+    /// space in LaTeX).
+    encoder: Option<fn(&str) -> String>,
+}
+
+impl ExtractedText {
+    pub fn with_encoder(encoder: fn(&str) -> String) -> ExtractedText {
+        ExtractedText { encoder: Some(encoder), ..Default::default() }
+    }
+
+    /// Appends a part of the source as is.
+    pub fn push_verbatim(&mut self, source_start: usize, text: &str) {
+        self.push(source_start, source_start + text.len(), text, SegmentKind::Verbatim);
+    }
+
+    /// Appends the prose standing for a range of the source (ex: a decoded entity).
+    pub fn push_replaced(&mut self, source_start: usize, source_end: usize, text: &str) {
+        self.push(source_start, source_end, text, SegmentKind::Replaced);
+    }
+
+    /// Appends the text standing for markup that fixes can’t change (ex: a line break tag).
+    pub fn push_hidden(&mut self, source_start: usize, source_end: usize, text: &str) {
+        self.push(source_start, source_end, text, SegmentKind::Hidden);
+    }
+
+    /// Appends a `PLACEHOLDER` standing for a range of the source (ex: inline code).
+    pub fn push_placeholder(&mut self, source_start: usize, source_end: usize) {
+        self.push_hidden(source_start, source_end, PLACEHOLDER);
+    }
+
+    /// Separates two blocks of prose, so that no rule matches across them.
+    pub fn push_break(&mut self, source_offset: usize) {
+        if !self.text.is_empty() && !self.text.ends_with("\n\n") {
+            let source_offset = source_offset.max(self.segments.last().map_or(0, |segment| segment.source_end));
+
+            self.push_hidden(source_offset, source_offset, "\n\n");
+        }
+    }
+
+    /// Starts a new catalogue entry, whose key is reported with the warnings raised on it.
+    pub fn push_entry(&mut self, source_offset: usize, key: &str) {
+        self.push_break(source_offset);
+        self.entries.push((self.text.len(), key.to_string()));
+    }
+
+    fn push(&mut self, source_start: usize, source_end: usize, text: &str, kind: SegmentKind) {
+        if text.is_empty() {
+            return;
+        }
+
+        let start = self.text.len();
+
+        self.text.push_str(text);
+        self.segments.push(Segment { start, end: self.text.len(), source_start, source_end, kind });
+    }
+
+    /// Maps a range of the prose to the range of the source it comes from.
+    pub fn source_range(&self, start: usize, end: usize) -> (usize, usize) {
+        let source_start = match self.segments.iter().find(|segment| start < segment.end) {
+            Some(segment) if segment.kind == SegmentKind::Verbatim => {
+                segment.source_start + start.saturating_sub(segment.start)
+            },
+            Some(segment) => segment.source_start,
+            None => self.segments.last().map_or(0, |segment| segment.source_end),
+        };
+
+        let source_end = match self.segments.iter().rev().find(|segment| end > segment.start) {
+            Some(segment) if segment.kind == SegmentKind::Verbatim => {
+                segment.source_start + end.min(segment.end) - segment.start
+            },
+            Some(segment) => segment.source_end,
+            None => source_start,
+        };
+
+        (source_start, source_end.max(source_start))
+    }
+
+    /// Maps a fix of the prose to the source. Fixes can change verbatim segments and replace whole
+    /// replaced segments, as long as they don’t cover any markup.
+    pub fn source_fix(&self, fix: &LinterFix) -> Option<LinterFix> {
+        let (first, start) = self.fix_boundary(fix.start, true)?;
+        let (last, end) = self.fix_boundary(fix.end, false)?;
+
+        let covered = &self.segments[first.min(last)..=first.max(last)];
+
+        let is_contiguous = covered
+            .windows(2)
+            .all(|pair| pair[0].source_end == pair[1].source_start);
+
+        if !is_contiguous || covered.iter().any(|segment| segment.kind == SegmentKind::Hidden) {
+            return None;
+        }
+
+        Some(LinterFix {
+            start,
+            end,
+            replacement: match self.encoder {
+                Some(encoder) => encoder(&fix.replacement),
+                None => fix.replacement.clone(),
+            },
+        })
+    }
+
+    /// Returns the index of the segment a fix starts or ends in, and the matching source offset.
+    fn fix_boundary(&self, offset: usize, is_start: bool) -> Option<(usize, usize)> {
+        let index = if is_start {
+            self.segments.iter().position(|segment| offset < segment.end)
+        } else {
+            self.segments.iter().rposition(|segment| offset > segment.start)
+        }?;
+
+        let segment = &self.segments[index];
+
+        match segment.kind {
+            SegmentKind::Verbatim => Some((index, segment.source_start + offset - segment.start)),
+            SegmentKind::Replaced if is_start && offset == segment.start => Some((index, segment.source_start)),
+            SegmentKind::Replaced if !is_start && offset == segment.end => Some((index, segment.source_end)),
+            _ => None,
+        }
+    }
+
+    /// Maps a warning raised on the prose to the source. Its fixes are dropped unless all of them
+    /// can be applied to the source.
+    pub fn source_warning(&self, mut warning: LinterWarning) -> LinterWarning {
+        warning.entry = self
+            .entries
+            .iter()
+            .rev()
+            .find(|(start, _)| *start <= warning.start)
+            .map(|(_, key)| key.clone());
+
+        (warning.start, warning.end) = self.source_range(warning.start, warning.end);
+
+        warning.fixes = warning
+            .fixes
+            .iter()
+            .map(|fix| self.source_fix(fix))
+            .collect::<Option<Vec<LinterFix>>>()
+            .unwrap_or_default();
+
+        warning
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extracted() -> ExtractedText {
+        // Extracted from "Use `x`, it's *fast*.".
+        let mut extracted = ExtractedText::default();
+
+        extracted.push_verbatim(0, "Use ");
+        extracted.push_placeholder(4, 7);
+        extracted.push_verbatim(7, ", it's ");
+        extracted.push_verbatim(15, "fast");
+        extracted.push_verbatim(20, ".");
+
+        extracted
+    }
+
+    #[test]
+    fn test_source_range() {
+        let extracted = extracted();
+
+        assert_eq!("Use \u{fffc}, it's fast.", extracted.text);
+        assert_eq!((0, 3), extracted.source_range(0, 3));
+        assert_eq!((4, 7), extracted.source_range(4, 7));
+        assert_eq!((3, 8), extracted.source_range(3, 8));
+        assert_eq!((11, 12), extracted.source_range(11, 12));
+        assert_eq!((15, 21), extracted.source_range(14, 19));
+    }
+
+    #[test]
+    fn test_source_fix() {
+        let extracted = extracted();
+
+        assert_eq!(
+            Some(LinterFix { start: 11, end: 12, replacement: "’".to_string() }),
+            extracted.source_fix(&LinterFix { start: 11, end: 12, replacement: "’".to_string() })
+        );
+        assert_eq!(None, extracted.source_fix(&LinterFix { start: 3, end: 8, replacement: String::new() }));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(InputFormat::Markdown, "md".parse().unwrap());
+        assert_eq!(InputFormat::Text, "Text".parse().unwrap());
+        assert!(matches!("docx".parse::<InputFormat>(), Err(LinterError::UnknownInputFormat(_))));
+        assert_eq!(InputFormat::Markdown, InputFormat::from_path(Path::new("docs/README.md")));
+        assert_eq!(InputFormat::Html, InputFormat::from_path(Path::new("index.HTML")));
+        assert_eq!(InputFormat::Text, InputFormat::from_path(Path::new("LICENSE")));
+        assert_eq!(InputFormat::Po, InputFormat::from_path(Path::new("locale/fr.po")));
+        assert_eq!(InputFormat::Xliff, "xlf".parse().unwrap());
+    }
+
+    #[test]
+    fn test_locale() {
+        assert_eq!(Some("fr".to_string()), InputFormat::Po.locale("msgid \"\"\nmsgstr \"\"\n\"Language: fr\\n\"\n", None));
+        assert_eq!(Some("de".to_string()), InputFormat::Json.locale("{}", Some(Path::new("i18n/de.json"))));
+        assert_eq!(None, InputFormat::Json.locale("{}", None));
+        assert_eq!(None, InputFormat::Markdown.locale("# Language: fr", None));
+    }
+}
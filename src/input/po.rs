@@ -0,0 +1,151 @@
+use std::sync::LazyLock;
+
+use super::message::{backslash_escape, escape_with_backslashes, MessageScanner};
+use super::*;
+
+/// The `Language` header of a catalogue.
+static LANGUAGE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"(?m)^"Language:\s*([\w-]+)"#).unwrap());
+
+/// Extracts the translations (`msgstr`) of a gettext PO catalogue, each of them being an entry
+/// keyed by its `msgid`. The header and obsolete entries are left out.
+pub fn extract(text: &str) -> ExtractedText {
+    let scanner = MessageScanner::new(backslash_escape);
+    let mut extracted = ExtractedText::with_encoder(escape_with_backslashes);
+
+    let mut msgid = String::new();
+    // The keyword the last string literals belong to (ex: `msgid`, `msgstr[1]`).
+    let mut keyword = "";
+    let mut line_start = 0;
+
+    for line in text.split_inclusive('\n') {
+        let offset = line_start;
+
+        line_start += line.len();
+
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        let (literal, is_continuation) = match trimmed.find('"') {
+            Some(0) => (trimmed, true),
+            Some(quote) => {
+                keyword = trimmed[..quote].trim_end();
+                (&trimmed[quote..], false)
+            },
+            None => continue,
+        };
+
+        let content = literal
+            .strip_prefix('"')
+            .and_then(|literal| literal.strip_suffix('"'))
+            .unwrap_or_default();
+        let content_start = offset + (line.len() - line.trim_start().len()) + (trimmed.len() - literal.len()) + 1;
+
+        match keyword {
+            "msgid" => {
+                if !is_continuation {
+                    msgid.clear();
+                }
+
+                msgid.push_str(&unescape(content));
+            },
+            // The header entry holds the metadata of the catalogue.
+            _ if keyword.starts_with("msgstr") && !msgid.is_empty() => {
+                if !is_continuation {
+                    let key = match keyword.strip_prefix("msgstr[") {
+                        Some(index) => format!("{}[{}", msgid, index),
+                        None => msgid.clone(),
+                    };
+
+                    extracted.push_entry(content_start, &key);
+                }
+
+                scanner.push(&mut extracted, content_start, content);
+            },
+            _ => {},
+        }
+    }
+
+    extracted
+}
+
+/// Returns the locale of the `Language` header of the catalogue.
+pub fn locale(text: &str) -> Option<String> {
+    LANGUAGE_REGEX.captures(text).map(|captures| captures[1].to_string())
+}
+
+fn unescape(literal: &str) -> String {
+    let mut unescaped = String::with_capacity(literal.len());
+    let mut position = 0;
+
+    while let Some(c) = literal[position..].chars().next() {
+        match backslash_escape(&literal[position..]) {
+            Some((length, message::Markup::Decoded(decoded))) => {
+                unescaped.push_str(&decoded);
+                position += length;
+            },
+            _ => {
+                unescaped.push(c);
+                position += c.len_utf8();
+            },
+        }
+    }
+
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CATALOGUE: &str = r#"# French translations.
+msgid ""
+msgstr ""
+"Language: fr_CA\n"
+"Content-Type: text/plain; charset=UTF-8\n"
+
+#: src/main.c:12
+#, c-format
+msgid "It's %s!"
+msgstr "C'est %s!"
+
+msgid "one file"
+msgid_plural "%d files"
+msgstr[0] "%d fichier"
+msgstr[1] ""
+"%d \"fichiers\""
+
+#~ msgid "Obsolete"
+#~ msgstr "Obsolète!"
+"#;
+
+    #[test]
+    fn test_extract() {
+        let extracted = extract(CATALOGUE);
+
+        assert_eq!("C'est \u{fffc}!\n\n\u{fffc} fichier\n\n\u{fffc} \"fichiers\"", extracted.text);
+    }
+
+    #[test]
+    fn test_extract_maps_entries() {
+        let extracted = extract(CATALOGUE);
+        let warning = extracted.source_warning(LinterWarning {
+            start: 29,
+            end: 30,
+            fixes: vec![LinterFix { start: 29, end: 30, replacement: "«\u{a0}".to_string() }],
+            ..Default::default()
+        });
+
+        assert_eq!(Some("one file[1]".to_string()), warning.entry);
+        assert_eq!("\\\"", &CATALOGUE[warning.start..warning.end]);
+        assert_eq!("«\u{a0}", warning.fixes[0].replacement);
+    }
+
+    #[test]
+    fn test_locale() {
+        assert_eq!(Some("fr_CA".to_string()), locale(CATALOGUE));
+        assert_eq!(None, locale("msgid \"\"\nmsgstr \"\"\n"));
+    }
+}
@@ -0,0 +1,155 @@
+use std::sync::LazyLock;
+
+use super::html::decode_reference;
+use super::message::{Markup, MessageScanner};
+use super::*;
+
+/// Units (`<trans-unit>` in XLIFF 1.2, `<unit>` in 2.0), with their `id`.
+static UNIT_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"<(?:trans-unit|unit)\b[^>]*?\bid\s*=\s*["']([^"']*)["']"#).unwrap());
+
+/// Translations, with their content unless they are empty elements.
+static TARGET_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"<target\b[^>]*?(?:/>|>((?s).*?)</target>)").unwrap());
+
+/// The target language attribute of XLIFF 1.2 and 2.0 documents.
+static TARGET_LANGUAGE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"\b(?:target-language|trgLang)\s*=\s*["']([\w-]+)["']"#).unwrap());
+
+/// Inline elements standing for a word of the message.
+const PLACEHOLDER_ELEMENTS: &[&str] = &["ph", "x"];
+
+/// Inline elements whose content is native code (ex: the `<b>` of a `<bpt>`), not prose.
+const CODE_ELEMENTS: &[&str] = &["bpt", "ept", "it", "ph"];
+
+/// Extracts the translations (`<target>`) of an XLIFF 1.2 or 2.0 document, each of them being an
+/// entry keyed by the `id` of its unit. Inline codes (`<ph>`, `<x/>`, `<bpt>`…) are left out.
+pub fn extract(text: &str) -> ExtractedText {
+    let scanner = MessageScanner::new(xml_markup);
+    let mut extracted = ExtractedText::with_encoder(escape_xml);
+    let mut units = UNIT_REGEX.captures_iter(text).peekable();
+    let mut key = String::new();
+
+    for target in TARGET_REGEX.captures_iter(text) {
+        let content = match target.get(1) {
+            Some(content) => content,
+            None => continue,
+        };
+
+        while let Some(unit) = units.next_if(|unit| unit.get(0).unwrap().start() < content.start()) {
+            key = unit[1].to_string();
+        }
+
+        extracted.push_entry(content.start(), &key);
+        scanner.push(&mut extracted, content.start(), content.as_str());
+    }
+
+    extracted
+}
+
+/// Returns the target language of the document.
+pub fn locale(text: &str) -> Option<String> {
+    TARGET_LANGUAGE_REGEX.captures(text).map(|captures| captures[1].to_string())
+}
+
+/// Recognizes the entities, CDATA sections and inline elements of XLIFF messages.
+fn xml_markup(source: &str) -> Option<(usize, Markup)> {
+    if source.starts_with('&') {
+        let end = source.find(';').filter(|end| *end <= 32)?;
+
+        return decode_reference(&source[1..end]).map(|decoded| (end + 1, Markup::Decoded(decoded.to_string())));
+    }
+
+    if source.starts_with("<![CDATA[") {
+        return Some((9, Markup::Transparent));
+    }
+
+    if source.starts_with("]]>") {
+        return Some((3, Markup::Transparent));
+    }
+
+    let after_bracket = source.strip_prefix('<')?;
+
+    if !after_bracket.starts_with(|c: char| c.is_ascii_alphabetic() || c == '/') {
+        return None;
+    }
+
+    let length = source.find('>')? + 1;
+    let name_length = after_bracket
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == ':'))
+        .unwrap_or(after_bracket.len());
+    let name = &after_bracket[..name_length];
+    let is_self_closing = source[..length].ends_with("/>");
+
+    let length = if CODE_ELEMENTS.contains(&name) && !is_self_closing {
+        let closing_tag = format!("</{}>", name);
+
+        source.find(&closing_tag).map_or(length, |offset| offset + closing_tag.len())
+    } else {
+        length
+    };
+
+    if PLACEHOLDER_ELEMENTS.contains(&name) {
+        Some((length, Markup::Placeholder))
+    } else {
+        Some((length, Markup::Transparent))
+    }
+}
+
+/// Writes a text in XML character data.
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOCUMENT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<xliff version="1.2">
+  <file source-language="en" target-language="fr" datatype="plaintext">
+    <body>
+      <trans-unit id="greeting">
+        <source>Hello <x id="1"/>!</source>
+        <target>Bonjour <x id="1"/>!</target>
+      </trans-unit>
+      <trans-unit id="terms">
+        <source>Read the <bpt id="1">&lt;b&gt;</bpt>terms<ept id="1">&lt;/b&gt;</ept>...</source>
+        <target>Lisez les <g id="2">conditions</g> &amp; <bpt id="1">&lt;b&gt;</bpt>règles<ept id="1">&lt;/b&gt;</ept>...</target>
+      </trans-unit>
+      <trans-unit id="empty">
+        <target/>
+      </trans-unit>
+    </body>
+  </file>
+</xliff>
+"#;
+
+    #[test]
+    fn test_extract() {
+        let extracted = extract(DOCUMENT);
+
+        assert_eq!("Bonjour \u{fffc}!\n\nLisez les conditions & règles...", extracted.text);
+    }
+
+    #[test]
+    fn test_extract_maps_entries() {
+        let extracted = extract(DOCUMENT);
+        let warning = extracted.source_warning(LinterWarning {
+            start: 35,
+            end: 36,
+            fixes: vec![LinterFix { start: 35, end: 36, replacement: "&".to_string() }],
+            ..Default::default()
+        });
+
+        assert_eq!(Some("terms".to_string()), warning.entry);
+        assert_eq!("&amp;", &DOCUMENT[warning.start..warning.end]);
+        assert_eq!("&amp;", warning.fixes[0].replacement);
+    }
+
+    #[test]
+    fn test_locale() {
+        assert_eq!(Some("fr".to_string()), locale(DOCUMENT));
+        assert_eq!(Some("de-CH".to_string()), locale(r#"<xliff version="2.0" srcLang="en" trgLang="de-CH">"#));
+    }
+}
@@ -1,58 +1,191 @@
+extern crate glob;
+extern crate pulldown_cmark;
 extern crate regex;
 extern crate serde;
 #[macro_use] extern crate serde_derive;
 extern crate serde_json;
+extern crate toml;
 
+mod builder;
+pub mod config;
 pub mod errors;
-mod filters;
+pub mod filters;
+pub mod fixes;
+pub mod input;
+pub mod locale;
+mod number;
+pub mod output;
+pub mod report;
+mod suppressions;
 
-use errors::LinterWarning;
+pub use builder::LinterBuilder;
+pub use config::Config;
+pub use filters::LinterFilter;
+pub use input::InputFormat;
+pub use locale::{FrenchSpacing, Language, Locale};
+pub use output::OutputFormat;
+pub use report::Report;
+
+use errors::{LineIndex, LinterError, LinterFix, LinterWarning, Severity};
+use fixes::{apply_fixes, FixResult};
 use filters::*;
+use regex::{Regex, RegexSet};
+use std::sync::OnceLock;
+use suppressions::{Suppressions, UNUSED_SUPPRESSION_RULE};
+
+/// An active filter, with its regular expression compiled once.
+struct CompiledFilter {
+    filter: Box<dyn LinterFilter>,
+    regex: Regex,
+}
 
+/// Checks texts against a set of filters. Filters and their regular expressions are compiled
+/// once, when building the linter, so a linter should be reused across texts.
 pub struct Linter {
-    locale: String,
+    locale: Locale,
+    only: Option<Vec<String>>,
+    disabled: Vec<String>,
+    severities: Vec<(String, Severity)>,
+    filters: Vec<CompiledFilter>,
+    /// All the filters regular expressions, to find the filters matching a text in one pass.
+    /// Compiled on the first check, once all the filters have been registered.
+    regex_set: OnceLock<RegexSet>,
 }
 
 impl Linter {
-    pub fn new(locale: String) -> Result<Linter, LinterWarning> {
-        let linter = Linter { locale: locale };
+    pub fn new(locale: String) -> Result<Linter, LinterError> {
+        Linter::builder().locale(&locale).build()
+    }
 
-        Ok(linter)
+    pub fn builder() -> LinterBuilder {
+        LinterBuilder::default()
     }
 
-    pub fn check(&self, text: &str) -> Result<(), Vec<LinterWarning>> {
-        let mut warnings = Vec::<LinterWarning>::new();
+    pub fn check(&self, text: &str) -> Report {
+        self.check_as(text, InputFormat::Text)
+    }
 
-        for filter in &self.active_filters(self.locale.as_str()) {
-            let result = filter.check(text);
+    /// Checks the prose of a text in the given format, with warnings relative to `text`.
+    pub fn check_as(&self, text: &str, format: InputFormat) -> Report {
+        let mut warnings = match format.extract(text) {
+            Some(extracted) => self
+                .find_warnings(&extracted.text)
+                .into_iter()
+                .map(|warning| extracted.source_warning(warning))
+                .collect(),
+            None => self.find_warnings(text),
+        };
+
+        let suppressions = Suppressions::parse(text);
+
+        if !warnings.is_empty() || !suppressions.is_empty() {
+            let index = LineIndex::new(text);
+
+            for warning in &mut warnings {
+                warning.locate(&index);
+            }
+
+            if !suppressions.is_empty() {
+                warnings = suppressions.apply(warnings, &index, self.is_enabled(UNUSED_SUPPRESSION_RULE));
+            }
+        }
 
-            if result.is_err() {
-                warnings.append(&mut result.err().unwrap());
+        for warning in &mut warnings {
+            if let Some((_, severity)) = self.severities.iter().find(|(rule, _)| *rule == warning.rule) {
+                warning.severity = *severity;
             }
         }
 
-        if warnings.is_empty() {
-            Ok(())
-        } else {
-            Err(warnings)
+        Report { warnings }
+    }
+
+    fn find_warnings(&self, text: &str) -> Vec<LinterWarning> {
+        let mut warnings = Vec::<LinterWarning>::new();
+
+        for index in self.regex_set().matches(text).iter() {
+            let compiled = &self.filters[index];
+
+            warnings.append(&mut compiled.filter.check_with_regex(&compiled.regex, text));
         }
+
+        warnings
+    }
+
+    /// Returns `text` with all the non-overlapping fixes of the active filters applied.
+    pub fn fix(&self, text: &str) -> String {
+        self.fix_with_result(text).text
     }
 
-    fn active_filters(&self, locale: &str) -> Vec<Box<dyn LinterFilter>> {
-        self.filters(locale)
-            .into_iter()
-            .filter(|filter| filter.locales().is_empty() || filter.locales().contains(&locale))
-            .collect()
+    /// Same as `fix`, but also returns the applied fixes, with offsets relative to `text`.
+    pub fn fix_with_result(&self, text: &str) -> FixResult {
+        self.fix_as_with_result(text, InputFormat::Text)
     }
 
-    fn filters(&self, locale: &str) -> Vec<Box<dyn LinterFilter>> {
+    /// Returns `text` with the fixes of its prose applied, in the given format.
+    pub fn fix_as(&self, text: &str, format: InputFormat) -> String {
+        self.fix_as_with_result(text, format).text
+    }
+
+    /// Same as `fix_as`, but also returns the applied fixes, with offsets relative to `text`.
+    pub fn fix_as_with_result(&self, text: &str, format: InputFormat) -> FixResult {
+        let warnings = self.check_as(text, format).warnings;
+
+        apply_fixes(text, &warnings)
+    }
+
+    /// Registers a custom filter, which can be enabled and disabled like the built-in ones.
+    pub fn add_filter(&mut self, filter: Box<dyn LinterFilter>) -> Result<(), LinterError> {
+        self.register(filter)?;
+        self.regex_set = OnceLock::new();
+
+        Ok(())
+    }
+
+    /// Keeps the filter if it is active, compiling its regular expression.
+    fn register(&mut self, filter: Box<dyn LinterFilter>) -> Result<(), LinterError> {
+        let locales = filter.locales();
+        let is_active = (locales.is_empty() || locales.iter().any(|tag| self.locale.matches(tag)))
+            && self.is_enabled(filter.id());
+
+        if is_active {
+            let regex = Regex::new(&filter.regex_pattern())
+                .map_err(|error| LinterError::InvalidRegex { rule: filter.id().to_string(), error })?;
+
+            self.filters.push(CompiledFilter { filter, regex });
+        }
+
+        Ok(())
+    }
+
+    fn regex_set(&self) -> &RegexSet {
+        // The regular expressions have all been compiled on their own already.
+        self.regex_set.get_or_init(|| {
+            RegexSet::new(self.filters.iter().map(|compiled| compiled.regex.as_str()))
+                .expect("compiled regular expressions")
+        })
+    }
+
+    fn is_enabled(&self, rule: &str) -> bool {
+        let is_registered = match self.only {
+            Some(ref only) => only.iter().any(|enabled| enabled == rule),
+            None => true,
+        };
+
+        is_registered && !self.disabled.iter().any(|disabled| disabled == rule)
+    }
+
+    fn default_filters(locale: &Locale, spacing: FrenchSpacing) -> Vec<Box<dyn LinterFilter>> {
         vec![
             Box::new(CurlyApostropheFilter {}),
-            Box::new(EllipsisSymbolFilter {}),
+            Box::new(DashFilter { locale: locale.clone() }),
+            Box::new(EllipsisSymbolFilter { locale: locale.clone() }),
+            Box::new(InvertedPunctuationFilter {}),
             Box::new(NoSpaceBeforeCommaFilter {}),
-            Box::new(PriceFilter { locale: locale.to_string() }),
-            Box::new(QuotesFilter { locale: locale.to_string() }),
-            Box::new(SpaceBeforeDoublePonctuationFilter {}),
+            Box::new(NumberFormatFilter { locale: locale.clone(), spacing }),
+            Box::new(PriceFilter { locale: locale.clone(), spacing }),
+            Box::new(QuotesFilter { locale: locale.clone(), spacing }),
+            Box::new(SpaceBeforeDoublePonctuationFilter { spacing }),
+            Box::new(UnitFilter { locale: locale.clone(), spacing }),
         ]
     }
 }
@@ -60,16 +193,13 @@ impl Linter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use errors::Position;
 
     #[test]
     fn test_linter_with_two_warnings() {
         let linter = Linter::new("en".to_string()).unwrap();
 
-        let result = linter.check("It's me...");
-
-        assert!(result.is_err());
-
-        let warnings = result.err().unwrap();
+        let warnings = linter.check("It's me...").warnings;
 
         assert_eq!(2, warnings.len());
 
@@ -90,19 +220,14 @@ mod tests {
 
         let result = linter.check("It’s me…");
 
-        assert_eq!(false, result.is_err());
-        assert_eq!((), result.unwrap());
+        assert!(result.is_empty());
     }
 
     #[test]
     fn test_linter_with_a_language_specific_warning() {
         let linter = Linter::new("fr".to_string()).unwrap();
 
-        let result = linter.check("C’est moi!");
-
-        assert!(result.is_err());
-
-        let warnings = result.err().unwrap();
+        let warnings = linter.check("C’est moi!").warnings;
 
         assert_eq!(1, warnings.len());
         assert_eq!(
@@ -119,7 +244,279 @@ mod tests {
 
         let result = linter.check("It’s me!");
 
-        assert_eq!(false, result.is_err());
-        assert_eq!((), result.unwrap());
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn test_linter_fix() {
+        let linter = Linter::new("en".to_string()).unwrap();
+
+        assert_eq!("“It’s me…”", linter.fix("\"It's me...\""));
+    }
+
+    #[test]
+    fn test_linter_fix_with_result() {
+        let linter = Linter::new("fr".to_string()).unwrap();
+
+        let result = linter.fix_with_result("C’est moi! Et toi?");
+
+        assert_eq!("C’est moi\u{a0}! Et toi\u{a0}?", result.text);
+        assert_eq!(
+            vec![
+                LinterFix { start: 11, end: 11, replacement: "\u{a0}".to_string() },
+                LinterFix { start: 19, end: 19, replacement: "\u{a0}".to_string() },
+            ],
+            result.applied
+        );
+    }
+
+    #[test]
+    fn test_linter_warning_positions() {
+        let linter = Linter::new("en".to_string()).unwrap();
+
+        let warnings = linter.check("“Ça va ?”\nIt's 10 €.").warnings;
+
+        assert_eq!(2, warnings.len());
+
+        assert_eq!(17, warnings[0].start);
+        assert_eq!(Position { line: 2, column: 3, utf16_column: 3 }, warnings[0].start_position);
+        assert_eq!(Position { line: 2, column: 4, utf16_column: 4 }, warnings[0].end_position);
+
+        assert_eq!(Position { line: 2, column: 6, utf16_column: 6 }, warnings[1].start_position);
+        assert_eq!(Position { line: 2, column: 10, utf16_column: 10 }, warnings[1].end_position);
+    }
+
+    #[test]
+    fn test_linter_warning_rules_and_severities() {
+        let linter = Linter::new("en".to_string()).unwrap();
+
+        let warnings = linter.check("It's me , Mario...").warnings;
+
+        assert_eq!(3, warnings.len());
+
+        assert_eq!("curly-apostrophe", warnings[0].rule);
+        assert_eq!(Severity::Warning, warnings[0].severity);
+
+        assert_eq!("ellipsis-symbol", warnings[1].rule);
+        assert_eq!(Severity::Info, warnings[1].severity);
+
+        assert_eq!("no-space-before-comma", warnings[2].rule);
+        assert_eq!(Severity::Error, warnings[2].severity);
+    }
+
+    struct ProductNameFilter {}
+
+    impl LinterFilter for ProductNameFilter {
+        fn id(&self) -> &str {
+            "product-name"
+        }
+
+        fn message(&self) -> &str {
+            "Please write “GitHub” with a capital H."
+        }
+
+        fn regex_pattern(&self) -> String {
+            r"\bGithub\b".to_string()
+        }
+    }
+
+    #[test]
+    fn test_linter_with_a_custom_filter() {
+        let mut linter = Linter::new("en".to_string()).unwrap();
+
+        linter.add_filter(Box::new(ProductNameFilter {})).unwrap();
+
+        let warnings = linter.check("It's on Github.").warnings;
+
+        assert_eq!(2, warnings.len());
+        assert_eq!("product-name", warnings[1].rule);
+        assert_eq!(8, warnings[1].start);
+        assert_eq!(14, warnings[1].end);
+
+        let linter = Linter::builder()
+            .filter(Box::new(ProductNameFilter {}))
+            .only(&["product-name"])
+            .build()
+            .unwrap();
+
+        assert_eq!(1, linter.check("It's on Github.").warnings.len());
+    }
+
+    struct MalformedFixFilter {}
+
+    impl LinterFilter for MalformedFixFilter {
+        fn fixes(&self, text: &str, start: usize, _end: usize) -> Vec<LinterFix> {
+            vec![LinterFix { start, end: text.len() + 1, replacement: "GitHub".to_string() }]
+        }
+
+        fn id(&self) -> &str {
+            "malformed-fix"
+        }
+
+        fn message(&self) -> &str {
+            "Please write “GitHub” with a capital H."
+        }
+
+        fn regex_pattern(&self) -> String {
+            r"\bGithub\b".to_string()
+        }
+    }
+
+    #[test]
+    fn test_linter_fix_with_a_malformed_custom_fix() {
+        let linter = Linter::builder().filter(Box::new(MalformedFixFilter {})).build().unwrap();
+
+        assert_eq!("It’s on Github.", linter.fix("It's on Github."));
+    }
+
+    struct CountingFilter {
+        calls: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    impl LinterFilter for CountingFilter {
+        fn check_with_regex(&self, _regex: &Regex, _text: &str) -> Vec<LinterWarning> {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+
+            Vec::new()
+        }
+
+        fn id(&self) -> &str {
+            "counting"
+        }
+
+        fn message(&self) -> &str {
+            ""
+        }
+
+        fn regex_pattern(&self) -> String {
+            "[%]".to_string()
+        }
+    }
+
+    #[test]
+    fn test_linter_only_runs_the_matching_filters() {
+        let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+
+        let mut linter = Linter::new("en".to_string()).unwrap();
+
+        linter.add_filter(Box::new(CountingFilter { calls: calls.clone() })).unwrap();
+
+        assert!(linter.check("It’s 50%…").is_empty());
+        assert!(!linter.check("It's me...").is_empty());
+        assert!(linter.check("It’s 100%…").is_empty());
+
+        assert_eq!(2, calls.load(std::sync::atomic::Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_linter_with_a_regional_locale() {
+        let linter = Linter::new("fr-CA".to_string()).unwrap();
+
+        assert_eq!(1, linter.check("C’est moi!").warnings.len());
+    }
+
+    #[test]
+    fn test_linter_with_markdown() {
+        let linter = Linter::new("en".to_string()).unwrap();
+        let text = "It's `it's`...\n\n```\nlet s = \"...\";\n```\n\nSee [this](https://example.com/it's)...\n";
+
+        let warnings = linter.check_as(text, InputFormat::Markdown).warnings;
+
+        assert_eq!(3, warnings.len());
+        assert_eq!((2, 3), (warnings[0].start, warnings[0].end));
+        assert_eq!((11, 14), (warnings[1].start, warnings[1].end));
+        assert_eq!(Position { line: 7, column: 37, utf16_column: 37 }, warnings[2].start_position);
+
+        assert_eq!(
+            "It’s `it's`…\n\n```\nlet s = \"...\";\n```\n\nSee [this](https://example.com/it's)…\n",
+            linter.fix_as(text, InputFormat::Markdown)
+        );
+    }
+
+    #[test]
+    fn test_linter_with_html() {
+        let linter = Linter::new("fr".to_string()).unwrap();
+        let text = "<p class=\"note\">Oui&nbsp;! Non&#8239;? Peut-être!</p><pre>if (a) { b(\"c\"); }</pre>";
+
+        let warnings = linter.check_as(text, InputFormat::Html).warnings;
+
+        assert_eq!(1, warnings.len());
+        assert_eq!("space-before-double-punctuation", warnings[0].rule);
+        assert_eq!("e!", &text[warnings[0].start..warnings[0].end]);
+
+        assert_eq!(
+            "<p class=\"note\">Oui&nbsp;! Non&#8239;? Peut-être\u{a0}!</p><pre>if (a) { b(\"c\"); }</pre>",
+            linter.fix_as(text, InputFormat::Html)
+        );
+    }
+
+    #[test]
+    fn test_linter_with_latex() {
+        let linter = Linter::new("fr".to_string()).unwrap();
+        let text = "L'été~! \\og Oui \\fg{} coûte 10\\,€. C'est \"moi\"... $a'' = b$ % Ah!\nFin !";
+
+        let warnings = linter.check_as(text, InputFormat::Latex).warnings;
+
+        assert_eq!(
+            vec!["ellipsis-symbol", "quotes", "space-before-double-punctuation"],
+            warnings.iter().map(|warning| warning.rule.as_str()).collect::<Vec<&str>>()
+        );
+        assert_eq!(
+            "L'été~! \\og Oui \\fg{} coûte 10\\,€. C'est \\og moi\\fg{}\\ldots{} $a'' = b$ % Ah!\nFin~!",
+            linter.fix_as(text, InputFormat::Latex)
+        );
+    }
+
+    #[test]
+    fn test_linter_with_a_po_catalogue() {
+        let linter = Linter::new("fr".to_string()).unwrap();
+        let text = "msgid \"\"\nmsgstr \"\"\n\"Language: fr\\n\"\n\nmsgid \"Hello %s!\"\nmsgstr \"Bonjour %s, ça va!\"\n\n\
+                    msgid \"Say \\\"yes\\\"\"\nmsgstr \"Dites \\\"oui\\\"\"\n";
+
+        let warnings = linter.check_as(text, InputFormat::Po).warnings;
+
+        assert_eq!(
+            vec!["quotes", "space-before-double-punctuation"],
+            warnings.iter().map(|warning| warning.rule.as_str()).collect::<Vec<&str>>()
+        );
+        assert_eq!(Some("Say \"yes\""), warnings[0].entry.as_deref());
+        assert_eq!(Some("Hello %s!"), warnings[1].entry.as_deref());
+        assert_eq!(6, warnings[1].start_position.line);
+
+        assert_eq!(
+            "msgid \"\"\nmsgstr \"\"\n\"Language: fr\\n\"\n\nmsgid \"Hello %s!\"\nmsgstr \"Bonjour %s, ça va\u{a0}!\"\n\n\
+             msgid \"Say \\\"yes\\\"\"\nmsgstr \"Dites «\u{a0}oui\u{a0}»\"\n",
+            linter.fix_as(text, InputFormat::Po)
+        );
+    }
+
+    #[test]
+    fn test_linter_with_suppressions() {
+        let linter = Linter::new("en".to_string()).unwrap();
+        let text = "<!-- typolint-disable-next-line quotes, curly-apostrophe -->\n> \"It's me...\"\n\nDone...\n";
+
+        let warnings = linter.check_as(text, InputFormat::Markdown).warnings;
+
+        assert_eq!(
+            vec!["ellipsis-symbol", "ellipsis-symbol"],
+            warnings.iter().map(|warning| warning.rule.as_str()).collect::<Vec<&str>>()
+        );
+
+        let warnings = linter.check_as("It’s me… # typolint-disable-line ellipsis-symbol\n", InputFormat::Text).warnings;
+
+        assert_eq!(1, warnings.len());
+        assert_eq!("unused-suppression", warnings[0].rule);
+        assert_eq!(Position { line: 1, column: 10, utf16_column: 10 }, warnings[0].start_position);
+
+        let linter = Linter::builder().disable("unused-suppression").build().unwrap();
+
+        assert!(linter.check("It’s me… # typolint-disable-line").is_empty());
+    }
+
+    #[test]
+    fn test_linter_with_an_unsupported_locale() {
+        let result = Linter::new("pt".to_string());
+
+        assert!(matches!(result, Err(LinterError::UnsupportedLocale(ref locale)) if locale == "pt"));
     }
 }
@@ -0,0 +1,199 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::errors::LinterError;
+
+/// A language supported by the linter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Language {
+    De,
+    En,
+    Es,
+    Fr,
+    It,
+}
+
+impl Language {
+    /// Returns the ISO 639-1 code of the language.
+    pub fn code(&self) -> &'static str {
+        match *self {
+            Language::De => "de",
+            Language::En => "en",
+            Language::Es => "es",
+            Language::Fr => "fr",
+            Language::It => "it",
+        }
+    }
+}
+
+/// A locale parsed from a BCP 47 tag (ex: `fr`, `fr-CA`, `de-CH`, `en-GB`).
+///
+/// Only the language and region subtags are kept: rules are chosen by language, falling back from
+/// a region to its language when there is no region-specific rule.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Locale {
+    language: Language,
+    region: Option<String>,
+}
+
+impl Locale {
+    pub fn new(language: Language, region: Option<&str>) -> Locale {
+        Locale { language, region: region.map(str::to_uppercase) }
+    }
+
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// Returns the region subtag, in upper case (ex: `CA` for `fr-CA`).
+    pub fn region(&self) -> Option<&str> {
+        self.region.as_deref()
+    }
+
+    /// Returns whether a tag of a rule `locales()` (ex: `fr` or `fr-CA`) applies to the locale.
+    pub fn matches(&self, tag: &str) -> bool {
+        match tag.parse::<Locale>() {
+            Ok(locale) => locale.language == self.language && (locale.region.is_none() || locale.region == self.region),
+            Err(_) => false,
+        }
+    }
+}
+
+impl From<Language> for Locale {
+    fn from(language: Language) -> Locale {
+        Locale { language, region: None }
+    }
+}
+
+impl FromStr for Locale {
+    type Err = LinterError;
+
+    fn from_str(tag: &str) -> Result<Locale, LinterError> {
+        let invalid = || LinterError::InvalidLocale(tag.to_string());
+
+        let mut subtags = tag.split(['-', '_']);
+
+        let language = subtags.next().unwrap_or_default();
+
+        if !(2..=3).contains(&language.len()) || !language.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(invalid());
+        }
+
+        let language = match language.to_lowercase().as_str() {
+            "de" | "deu" | "ger" => Language::De,
+            "en" | "eng" => Language::En,
+            "es" | "spa" => Language::Es,
+            "fr" | "fra" | "fre" => Language::Fr,
+            "it" | "ita" => Language::It,
+            _ => return Err(LinterError::UnsupportedLocale(tag.to_string())),
+        };
+
+        let mut region = None;
+
+        for subtag in subtags {
+            let is_script = subtag.len() == 4 && subtag.chars().all(|c| c.is_ascii_alphabetic());
+            let is_region = (subtag.len() == 2 && subtag.chars().all(|c| c.is_ascii_alphabetic()))
+                || (subtag.len() == 3 && subtag.chars().all(|c| c.is_ascii_digit()));
+
+            if subtag.is_empty() || !subtag.chars().all(|c| c.is_ascii_alphanumeric()) {
+                return Err(invalid());
+            } else if is_script && region.is_none() {
+                continue;
+            } else if is_region && region.is_none() {
+                region = Some(subtag);
+            } else {
+                // Variants and extensions don’t change typographic rules.
+                break;
+            }
+        }
+
+        Ok(Locale::new(language, region))
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.region {
+            Some(ref region) => write!(f, "{}-{}", self.language.code(), region),
+            None => f.write_str(self.language.code()),
+        }
+    }
+}
+
+/// How strictly French non-breaking spaces are checked, before `;`, `:`, `!`, `?` and the
+/// currency signs, inside `« »` and between groups of digits.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FrenchSpacing {
+    /// Imprimerie nationale rules: a narrow non-breaking space (U+202F) before `;`, `!` and `?`
+    /// and between groups of digits, and a non-breaking space (U+00A0) before `:`, the currency
+    /// signs and units, and inside `« »`.
+    Strict,
+    /// Either non-breaking space, fixes inserting U+00A0.
+    #[default]
+    Lenient,
+    /// Only U+00A0, as narrow non-breaking spaces are missing from some fonts.
+    WebSafe,
+}
+
+impl FrenchSpacing {
+    /// Returns the space expected before `mark` (or after it, for `«`, and between groups of
+    /// digits, for digits).
+    pub fn space(&self, mark: char) -> char {
+        match (*self, mark) {
+            (FrenchSpacing::Strict, ';' | '!' | '?' | '0'..='9') => '\u{202f}',
+            _ => '\u{a0}',
+        }
+    }
+
+    /// Returns the non-breaking spaces accepted before `mark` (or after it, for `«`).
+    pub fn accepted_spaces(&self, mark: char) -> String {
+        match *self {
+            FrenchSpacing::Lenient => "\u{a0}\u{202f}".to_string(),
+            FrenchSpacing::Strict | FrenchSpacing::WebSafe => self.space(mark).to_string(),
+        }
+    }
+
+    /// Returns the non-breaking spaces rejected before `mark` (or after it, for `«`).
+    pub fn rejected_spaces(&self, mark: char) -> String {
+        let accepted = self.accepted_spaces(mark);
+
+        ['\u{a0}', '\u{202f}'].iter().filter(|space| !accepted.contains(**space)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Locale::new(Language::Fr, None), "fr".parse().unwrap());
+        assert_eq!(Locale::new(Language::Fr, Some("CA")), "fr-CA".parse().unwrap());
+        assert_eq!(Locale::new(Language::De, Some("CH")), "de_ch".parse().unwrap());
+        assert_eq!(Locale::new(Language::En, Some("GB")), "en-Latn-GB".parse().unwrap());
+        assert_eq!(Locale::new(Language::Es, Some("419")), "es-419".parse().unwrap());
+        assert_eq!(Locale::new(Language::It, None), "it-x-private".parse().unwrap());
+        assert_eq!("en-GB", "EN-gb".parse::<Locale>().unwrap().to_string());
+    }
+
+    #[test]
+    fn test_parse_with_errors() {
+        assert!(matches!("pt".parse::<Locale>(), Err(LinterError::UnsupportedLocale(ref tag)) if tag == "pt"));
+        assert!(matches!("pt-BR".parse::<Locale>(), Err(LinterError::UnsupportedLocale(ref tag)) if tag == "pt-BR"));
+        assert!(matches!("".parse::<Locale>(), Err(LinterError::InvalidLocale(ref tag)) if tag.is_empty()));
+        assert!(matches!("french".parse::<Locale>(), Err(LinterError::InvalidLocale(ref tag)) if tag == "french"));
+        assert!(matches!("fr-".parse::<Locale>(), Err(LinterError::InvalidLocale(ref tag)) if tag == "fr-"));
+    }
+
+    #[test]
+    fn test_matches() {
+        let locale: Locale = "fr-CA".parse().unwrap();
+
+        assert!(locale.matches("fr"));
+        assert!(locale.matches("fr-CA"));
+        assert!(!locale.matches("fr-FR"));
+        assert!(!locale.matches("en"));
+        assert!(!Locale::from(Language::Fr).matches("fr-CA"));
+    }
+}
@@ -0,0 +1,137 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+/// Numbers, with their digits possibly grouped by three and a fractional part (ex: `1,200.50`,
+/// `1.200,50`, `1 200,50` or `12345`), whatever the locale.
+pub const NUMBER: &str = r"[0-9]{1,3}(?:[ \u{a0}\u{202f}.,][0-9]{3})+(?:[.,][0-9]+)?|[0-9]+(?:[.,][0-9]+)?";
+
+/// `NUMBER`, compiled once for the filters looking for numbers in their matches.
+pub static NUMBER_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(NUMBER).unwrap());
+
+/// Integers with fewer digits don’t need to be grouped (ex: `1200` or `1 200`).
+const GROUPING_MIN_DIGITS: usize = 5;
+
+/// A number matched by `NUMBER`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Number {
+    pub integer: String,
+    pub fraction: Option<String>,
+    /// Whether the digits of the integer part are grouped.
+    pub is_grouped: bool,
+}
+
+impl Number {
+    /// Parses a number, `decimal_mark` telling whether a single `.` or `,` before three digits
+    /// (ex: `1,200`) is a decimal mark or a thousands separator, and `has_spaced_groups` whether
+    /// the locale groups digits with spaces. Returns `None` for anything else than a number (ex:
+    /// `1.2.3`, or `3 100` where digits aren’t grouped with spaces).
+    pub fn parse(text: &str, decimal_mark: char, has_spaced_groups: bool) -> Option<Number> {
+        let mut groups = vec![String::new()];
+        let mut separators = Vec::new();
+
+        for character in text.chars() {
+            if character.is_ascii_digit() {
+                groups.last_mut().unwrap().push(character);
+            } else {
+                separators.push(character);
+                groups.push(String::new());
+            }
+        }
+
+        let fraction = match separators.split_last() {
+            Some((&last, others)) if last == '.' || last == ',' => {
+                let is_decimal = if others.is_empty() {
+                    groups[1].len() != 3 || last == decimal_mark
+                } else {
+                    !others.contains(&last)
+                };
+
+                if is_decimal {
+                    separators.pop();
+                    groups.pop()
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+
+        if let Some(&separator) = separators.first() {
+            let is_grouped = separators.iter().all(|other| *other == separator)
+                && (1..=3).contains(&groups[0].len())
+                && groups[1..].iter().all(|group| group.len() == 3);
+
+            // Spaces also separate numbers that follow one another (ex: `in 3 100 pages`), unless
+            // they group digits in the locale.
+            let is_separate =
+                separator == ' ' && !has_spaced_groups && groups.concat().len() < GROUPING_MIN_DIGITS;
+
+            if !is_grouped || is_separate {
+                return None;
+            }
+        }
+
+        Some(Number { integer: groups.concat(), fraction, is_grouped: !separators.is_empty() })
+    }
+
+    /// Formats the number with the given separators, grouping the digits of long integers, and of
+    /// shorter ones if they were grouped.
+    pub fn format(&self, group_separator: char, decimal_mark: char) -> String {
+        let mut formatted = String::new();
+
+        if self.integer.len() >= GROUPING_MIN_DIGITS || (self.is_grouped && self.integer.len() > 3) {
+            for (index, digit) in self.integer.chars().enumerate() {
+                if index > 0 && (self.integer.len() - index) % 3 == 0 {
+                    formatted.push(group_separator);
+                }
+
+                formatted.push(digit);
+            }
+        } else {
+            formatted.push_str(&self.integer);
+        }
+
+        if let Some(ref fraction) = self.fraction {
+            formatted.push(decimal_mark);
+            formatted.push_str(fraction);
+        }
+
+        formatted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn number(integer: &str, fraction: Option<&str>, is_grouped: bool) -> Option<Number> {
+        Some(Number { integer: integer.to_string(), fraction: fraction.map(str::to_string), is_grouped })
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(number("1200", Some("50"), true), Number::parse("1,200.50", '.', false));
+        assert_eq!(number("1200", Some("50"), true), Number::parse("1.200,50", '.', false));
+        assert_eq!(number("1200", Some("50"), true), Number::parse("1\u{202f}200,50", ',', true));
+        assert_eq!(number("1200", None, true), Number::parse("1,200", '.', false));
+        assert_eq!(number("1", Some("200"), false), Number::parse("1,200", ',', false));
+        assert_eq!(number("1", Some("5"), false), Number::parse("1.5", ',', false));
+        assert_eq!(number("1200300", None, true), Number::parse("1 200 300", ',', false));
+        assert_eq!(number("12345", None, false), Number::parse("12345", ',', false));
+        assert_eq!(None, Number::parse("1.2.3", '.', false));
+        assert_eq!(None, Number::parse("1,200,3", '.', false));
+        assert_eq!(None, Number::parse("3 100", '.', false));
+        assert_eq!(number("3100", None, true), Number::parse("3 100", ',', true));
+    }
+
+    #[test]
+    fn test_format() {
+        let number = Number::parse("1234567,5", ',', false).unwrap();
+
+        assert_eq!("1,234,567.5", number.format(',', '.'));
+        assert_eq!("1\u{202f}234\u{202f}567,5", number.format('\u{202f}', ','));
+        assert_eq!("1200", Number::parse("1200", '.', false).unwrap().format(',', '.'));
+        assert_eq!("1.200", Number::parse("1,200", '.', false).unwrap().format('.', ','));
+    }
+}
@@ -0,0 +1,434 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+use serde_json::json;
+
+use super::*;
+
+const TOOL_NAME: &str = "typographic-linter";
+
+const TOOL_URI: &str = "https://github.com/rlustin/typographic-linter";
+
+/// The warnings raised on a file.
+#[derive(Debug, Default, Serialize)]
+pub struct FileWarnings {
+    pub path: String,
+    pub warnings: Vec<LinterWarning>,
+    /// The linted text, for the outputs identifying warnings by the text they were raised on.
+    #[serde(skip)]
+    pub text: String,
+}
+
+/// A format to write the warnings of several files in, for people or for CI services.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OutputFormat {
+    /// One `path:line:column: severity: message [rule]` line per warning.
+    #[default]
+    Text,
+    /// The `FileWarnings` serialized as a JSON array.
+    Json,
+    /// SARIF 2.1.0, for GitHub code scanning.
+    Sarif,
+    /// Checkstyle XML, for Jenkins and most CI services.
+    Checkstyle,
+    /// JUnit XML, with a test suite per file and a failed test case per warning.
+    Junit,
+    /// GitLab Code Quality JSON.
+    Gitlab,
+}
+
+impl OutputFormat {
+    pub fn write(&self, output: &mut dyn Write, results: &[FileWarnings]) -> io::Result<()> {
+        match *self {
+            OutputFormat::Text => write_text(output, results),
+            OutputFormat::Json => write_json(output, &results),
+            OutputFormat::Sarif => write_json(output, &sarif(results)),
+            OutputFormat::Checkstyle => write_checkstyle(output, results),
+            OutputFormat::Junit => write_junit(output, results),
+            OutputFormat::Gitlab => write_json(output, &gitlab_code_quality(results)),
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = LinterError;
+
+    fn from_str(format: &str) -> Result<OutputFormat, LinterError> {
+        match format.to_lowercase().as_str() {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "sarif" => Ok(OutputFormat::Sarif),
+            "checkstyle" => Ok(OutputFormat::Checkstyle),
+            "junit" => Ok(OutputFormat::Junit),
+            "gitlab" | "codequality" => Ok(OutputFormat::Gitlab),
+            _ => Err(LinterError::UnknownOutputFormat(format.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            OutputFormat::Text => "text",
+            OutputFormat::Json => "json",
+            OutputFormat::Sarif => "sarif",
+            OutputFormat::Checkstyle => "checkstyle",
+            OutputFormat::Junit => "junit",
+            OutputFormat::Gitlab => "gitlab",
+        })
+    }
+}
+
+fn write_text(output: &mut dyn Write, results: &[FileWarnings]) -> io::Result<()> {
+    for result in results {
+        for warning in &result.warnings {
+            write!(
+                output,
+                "{}:{}:{}: {}: {} [{}]",
+                result.path,
+                warning.start_position.line,
+                warning.start_position.column,
+                warning.severity,
+                warning.message,
+                warning.rule
+            )?;
+
+            match warning.entry {
+                Some(ref entry) => writeln!(output, " (entry: {})", entry)?,
+                None => writeln!(output)?,
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn write_json<T: serde::Serialize>(output: &mut dyn Write, value: &T) -> io::Result<()> {
+    serde_json::to_writer_pretty(&mut *output, value)?;
+
+    writeln!(output)
+}
+
+fn sarif(results: &[FileWarnings]) -> serde_json::Value {
+    let rules = results
+        .iter()
+        .flat_map(|result| result.warnings.iter().map(|warning| warning.rule.as_str()))
+        .collect::<BTreeSet<&str>>()
+        .into_iter()
+        .collect::<Vec<&str>>();
+
+    let sarif_results = results
+        .iter()
+        .flat_map(|result| result.warnings.iter().map(move |warning| (result, warning)))
+        .map(|(result, warning)| {
+            json!({
+                "ruleId": warning.rule,
+                "ruleIndex": rules.iter().position(|rule| *rule == warning.rule),
+                "level": match warning.severity {
+                    Severity::Error => "error",
+                    Severity::Warning => "warning",
+                    Severity::Info => "note",
+                },
+                "message": { "text": warning.message },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": result.path.replace('\\', "/") },
+                        // SARIF columns are counted in UTF-16 code units by default.
+                        "region": {
+                            "startLine": warning.start_position.line,
+                            "startColumn": warning.start_position.utf16_column,
+                            "endLine": warning.end_position.line,
+                            "endColumn": warning.end_position.utf16_column,
+                        },
+                    },
+                }],
+            })
+        })
+        .collect::<Vec<serde_json::Value>>();
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": TOOL_NAME,
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": TOOL_URI,
+                    "rules": rules.iter().map(|rule| json!({ "id": rule })).collect::<Vec<serde_json::Value>>(),
+                },
+            },
+            "results": sarif_results,
+        }],
+    })
+}
+
+fn write_checkstyle(output: &mut dyn Write, results: &[FileWarnings]) -> io::Result<()> {
+    writeln!(output, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(output, r#"<checkstyle version="4.3">"#)?;
+
+    for result in results {
+        writeln!(output, r#"  <file name="{}">"#, escape_xml(&result.path))?;
+
+        for warning in &result.warnings {
+            writeln!(
+                output,
+                r#"    <error line="{}" column="{}" severity="{}" message="{}" source="{}.{}"/>"#,
+                warning.start_position.line,
+                warning.start_position.column,
+                warning.severity,
+                escape_xml(&warning.message),
+                TOOL_NAME,
+                escape_xml(&warning.rule)
+            )?;
+        }
+
+        writeln!(output, "  </file>")?;
+    }
+
+    writeln!(output, "</checkstyle>")
+}
+
+fn write_junit(output: &mut dyn Write, results: &[FileWarnings]) -> io::Result<()> {
+    let count = results.iter().map(|result| result.warnings.len()).sum::<usize>();
+    // Files without warnings are a passing test case.
+    let tests = results.iter().map(|result| result.warnings.len().max(1)).sum::<usize>();
+
+    writeln!(output, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(output, r#"<testsuites name="{}" tests="{}" failures="{}">"#, TOOL_NAME, tests, count)?;
+
+    for result in results {
+        let path = escape_xml(&result.path);
+
+        writeln!(
+            output,
+            r#"  <testsuite name="{}" tests="{}" failures="{}" errors="0">"#,
+            path,
+            result.warnings.len().max(1),
+            result.warnings.len()
+        )?;
+
+        if result.warnings.is_empty() {
+            writeln!(output, r#"    <testcase name="{}" classname="{}"/>"#, TOOL_NAME, path)?;
+        }
+
+        for warning in &result.warnings {
+            let location = format!(
+                "{}:{}:{}",
+                result.path, warning.start_position.line, warning.start_position.column
+            );
+
+            writeln!(
+                output,
+                r#"    <testcase name="{}: {}" classname="{}">"#,
+                escape_xml(&location),
+                escape_xml(&warning.rule),
+                path
+            )?;
+            writeln!(
+                output,
+                r#"      <failure message="{}" type="{}">{}: {}: {} [{}]</failure>"#,
+                escape_xml(&warning.message),
+                warning.severity,
+                escape_xml(&location),
+                warning.severity,
+                escape_xml(&warning.message),
+                escape_xml(&warning.rule)
+            )?;
+            writeln!(output, "    </testcase>")?;
+        }
+
+        writeln!(output, "  </testsuite>")?;
+    }
+
+    writeln!(output, "</testsuites>")
+}
+
+/// Hashes the given parts with 64-bit FNV-1a, whose result doesn’t change across Rust versions.
+fn fnv1a(parts: &[&[u8]]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+
+    for part in parts {
+        // Each part is followed by a null byte, for `("ab", "c")` not to be hashed as `("a", "bc")`.
+        for byte in part.iter().chain(&[0]) {
+            hash ^= u64::from(*byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    hash
+}
+
+fn gitlab_code_quality(results: &[FileWarnings]) -> serde_json::Value {
+    let issues = results
+        .iter()
+        .flat_map(|result| {
+            // Identical warnings of a file are told apart by their order.
+            let mut occurrences = HashMap::new();
+
+            result.warnings.iter().map(move |warning| {
+                let matched = result.text.get(warning.start..warning.end).unwrap_or_default();
+                let occurrence = occurrences.entry((&warning.rule, matched)).or_insert(0u64);
+
+                *occurrence += 1;
+
+                let parts = [result.path.as_bytes(), warning.rule.as_bytes(), matched.as_bytes(), &occurrence.to_be_bytes()];
+
+                (result, warning, fnv1a(&parts))
+            })
+        })
+        .map(|(result, warning, fingerprint)| {
+            // Identifies the issue across pipelines, for GitLab to tell new issues from fixed ones,
+            // even when the lines around it change.
+            json!({
+                "description": warning.message,
+                "check_name": warning.rule,
+                "fingerprint": format!("{:016x}", fingerprint),
+                "severity": match warning.severity {
+                    Severity::Error => "major",
+                    Severity::Warning => "minor",
+                    Severity::Info => "info",
+                },
+                "location": {
+                    "path": result.path,
+                    "positions": {
+                        "begin": { "line": warning.start_position.line, "column": warning.start_position.column },
+                        "end": { "line": warning.end_position.line, "column": warning.end_position.column },
+                    },
+                },
+            })
+        })
+        .collect::<Vec<serde_json::Value>>();
+
+    serde_json::Value::Array(issues)
+}
+
+/// Escapes a text for XML character data and attribute values.
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use errors::Position;
+
+    fn results() -> Vec<FileWarnings> {
+        vec![
+            FileWarnings {
+                path: "docs/a&b.md".to_string(),
+                warnings: vec![LinterWarning {
+                    rule: "quotes".to_string(),
+                    severity: Severity::Error,
+                    message: "Please use “curly” quotes.".to_string(),
+                    start: 10,
+                    end: 15,
+                    start_position: Position { line: 2, column: 3, utf16_column: 4 },
+                    end_position: Position { line: 2, column: 8, utf16_column: 9 },
+                    ..Default::default()
+                }],
+                text: "See them: \"curly\" quotes.".to_string(),
+            },
+            FileWarnings { path: "README.md".to_string(), ..Default::default() },
+        ]
+    }
+
+    fn write(format: OutputFormat) -> String {
+        let mut output = Vec::new();
+
+        format.write(&mut output, &results()).unwrap();
+
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!("docs/a&b.md:2:3: error: Please use “curly” quotes. [quotes]\n", write(OutputFormat::Text));
+    }
+
+    #[test]
+    fn test_sarif() {
+        let sarif: serde_json::Value = serde_json::from_str(&write(OutputFormat::Sarif)).unwrap();
+        let result = &sarif["runs"][0]["results"][0];
+
+        assert_eq!("2.1.0", sarif["version"]);
+        assert_eq!("quotes", sarif["runs"][0]["tool"]["driver"]["rules"][0]["id"]);
+        assert_eq!("quotes", result["ruleId"]);
+        assert_eq!("error", result["level"]);
+        assert_eq!("docs/a&b.md", result["locations"][0]["physicalLocation"]["artifactLocation"]["uri"]);
+        assert_eq!(4, result["locations"][0]["physicalLocation"]["region"]["startColumn"]);
+    }
+
+    #[test]
+    fn test_checkstyle() {
+        assert_eq!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <checkstyle version=\"4.3\">\n  \
+             <file name=\"docs/a&amp;b.md\">\n    \
+             <error line=\"2\" column=\"3\" severity=\"error\" message=\"Please use “curly” quotes.\" source=\"typographic-linter.quotes\"/>\n  \
+             </file>\n  \
+             <file name=\"README.md\">\n  \
+             </file>\n\
+             </checkstyle>\n",
+            write(OutputFormat::Checkstyle)
+        );
+    }
+
+    #[test]
+    fn test_junit() {
+        let junit = write(OutputFormat::Junit);
+
+        assert!(junit.contains("<testsuites name=\"typographic-linter\" tests=\"2\" failures=\"1\">"));
+        assert!(junit.contains("<testsuite name=\"docs/a&amp;b.md\" tests=\"1\" failures=\"1\" errors=\"0\">"));
+        assert!(junit.contains("<testcase name=\"docs/a&amp;b.md:2:3: quotes\" classname=\"docs/a&amp;b.md\">"));
+        assert!(junit.contains("<testcase name=\"typographic-linter\" classname=\"README.md\"/>"));
+    }
+
+    #[test]
+    fn test_gitlab_code_quality() {
+        let issues: serde_json::Value = serde_json::from_str(&write(OutputFormat::Gitlab)).unwrap();
+
+        assert_eq!(1, issues.as_array().unwrap().len());
+        assert_eq!("quotes", issues[0]["check_name"]);
+        assert_eq!("major", issues[0]["severity"]);
+        assert_eq!("docs/a&b.md", issues[0]["location"]["path"]);
+        assert_eq!(2, issues[0]["location"]["positions"]["begin"]["line"]);
+        assert_eq!(16, issues[0]["fingerprint"].as_str().unwrap().len());
+    }
+
+    #[test]
+    fn test_gitlab_code_quality_fingerprints() {
+        let mut results = results();
+
+        results[0].text.insert_str(0, "Title\n\n");
+        results[0].warnings[0].start += 7;
+        results[0].warnings[0].end += 7;
+
+        let moved = gitlab_code_quality(&results);
+        let issues: serde_json::Value = serde_json::from_str(&write(OutputFormat::Gitlab)).unwrap();
+
+        assert_eq!(issues[0]["fingerprint"], moved[0]["fingerprint"]);
+        assert_eq!("e4789b1892e86724", format!("{:016x}", fnv1a(&[b"quotes"])));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(OutputFormat::Sarif, "SARIF".parse().unwrap());
+        assert!(matches!("html".parse::<OutputFormat>(), Err(LinterError::UnknownOutputFormat(_))));
+    }
+}
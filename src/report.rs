@@ -0,0 +1,53 @@
+use super::*;
+
+/// The warnings raised by a `Linter` on a text, in the order of its filters.
+#[derive(Debug, Default, Serialize)]
+pub struct Report {
+    pub warnings: Vec<LinterWarning>,
+}
+
+impl Report {
+    pub fn is_empty(&self) -> bool {
+        self.warnings.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.warnings.len()
+    }
+
+    /// Returns whether at least one warning has the `error` severity.
+    pub fn has_errors(&self) -> bool {
+        self.warnings.iter().any(|warning| warning.severity == Severity::Error)
+    }
+}
+
+impl IntoIterator for Report {
+    type Item = LinterWarning;
+    type IntoIter = std::vec::IntoIter<LinterWarning>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.warnings.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_has_errors() {
+        let mut report = Report::default();
+
+        assert!(report.is_empty());
+        assert!(!report.has_errors());
+
+        report.warnings.push(LinterWarning { severity: Severity::Info, ..Default::default() });
+
+        assert_eq!(1, report.len());
+        assert!(!report.has_errors());
+
+        report.warnings.push(LinterWarning { severity: Severity::Error, ..Default::default() });
+
+        assert!(report.has_errors());
+    }
+}
@@ -0,0 +1,272 @@
+use std::sync::LazyLock;
+
+use super::*;
+
+/// Identifier of the warnings raised on suppressions that suppress nothing.
+pub const UNUSED_SUPPRESSION_RULE: &str = "unused-suppression";
+
+/// A directive and the comment it’s written in, up to the end of the line.
+static DIRECTIVE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?m)(?:<!--|/\*|//|#|%)[ \t]*typolint-(disable-next-line|disable-line|disable|enable)\b([^\n]*?)[ \t]*(?:-->|\*/)?[ \t]*$",
+    )
+    .unwrap()
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DirectiveKind {
+    /// `typolint-disable`: suppresses the warnings up to the next `typolint-enable`, or to the end
+    /// of the text.
+    Disable,
+    /// `typolint-enable`: ends the `typolint-disable` blocks of all rules, or of the listed ones.
+    Enable,
+    /// `typolint-disable-line`: suppresses the warnings of the line of the comment.
+    DisableLine,
+    /// `typolint-disable-next-line`: suppresses the warnings of the line after the comment.
+    DisableNextLine,
+}
+
+/// A `typolint-…` directive, found in a comment of the linted text.
+#[derive(Debug)]
+struct Directive {
+    kind: DirectiveKind,
+    /// Identifiers of the suppressed rules, or all of them if empty.
+    rules: Vec<String>,
+    start: usize,
+    end: usize,
+    /// End of the block of a `typolint-disable` directive.
+    block_end: usize,
+}
+
+impl Directive {
+    fn applies_to(&self, rule: &str) -> bool {
+        self.rules.is_empty() || self.rules.iter().any(|suppressed| suppressed == rule)
+    }
+}
+
+/// The suppression directives of a text, written in HTML, Markdown, LaTeX, shell-like or C-like
+/// comments (ex: `<!-- typolint-disable-next-line quotes -->`, `# typolint-disable`).
+pub struct Suppressions {
+    directives: Vec<Directive>,
+}
+
+impl Suppressions {
+    pub fn parse(text: &str) -> Suppressions {
+        // Most texts have no directive.
+        if !text.contains("typolint-") {
+            return Suppressions { directives: Vec::new() };
+        }
+
+        let mut directives = DIRECTIVE_REGEX
+            .captures_iter(text)
+            .map(|captures| {
+                let kind = match &captures[1] {
+                    "disable" => DirectiveKind::Disable,
+                    "enable" => DirectiveKind::Enable,
+                    "disable-line" => DirectiveKind::DisableLine,
+                    _ => DirectiveKind::DisableNextLine,
+                };
+
+                // A justification can follow the rules (ex: `quotes -- quoting a tweet`).
+                let rules = captures[2].split(" --").next().unwrap_or_default();
+
+                Directive {
+                    kind,
+                    rules: rules
+                        .split(|c: char| c == ',' || c.is_whitespace())
+                        .filter(|rule| !rule.is_empty())
+                        .map(str::to_string)
+                        .collect(),
+                    start: captures.get(0).unwrap().start(),
+                    end: captures.get(0).unwrap().end(),
+                    block_end: text.len(),
+                }
+            })
+            .collect::<Vec<Directive>>();
+
+        for index in 0..directives.len() {
+            if directives[index].kind != DirectiveKind::Disable {
+                continue;
+            }
+
+            let disable = &directives[index];
+
+            let block_end = directives[index + 1..]
+                .iter()
+                .filter(|directive| directive.kind == DirectiveKind::Enable)
+                .find(|enable| {
+                    enable.rules.is_empty() || enable.rules.iter().any(|rule| disable.rules.contains(rule))
+                })
+                .map(|enable| enable.start);
+
+            if let Some(block_end) = block_end {
+                directives[index].block_end = block_end;
+            }
+        }
+
+        Suppressions { directives }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.directives.is_empty()
+    }
+
+    /// Removes the suppressed warnings, located with `index`, and warns about the unused
+    /// suppressions if `report_unused` is set.
+    pub fn apply(&self, warnings: Vec<LinterWarning>, index: &LineIndex, report_unused: bool) -> Vec<LinterWarning> {
+        // Whether each suppressed rule of each directive (or the whole directive) is used.
+        let mut used = self
+            .directives
+            .iter()
+            .map(|directive| vec![false; directive.rules.len().max(1)])
+            .collect::<Vec<Vec<bool>>>();
+
+        let lines = self
+            .directives
+            .iter()
+            .map(|directive| index.position(directive.start).line)
+            .collect::<Vec<usize>>();
+
+        let mut kept = warnings
+            .into_iter()
+            .filter(|warning| {
+                let mut is_suppressed = false;
+
+                for (position, directive) in self.directives.iter().enumerate() {
+                    let is_in_scope = match directive.kind {
+                        DirectiveKind::Disable => directive.end <= warning.start && warning.start < directive.block_end,
+                        DirectiveKind::Enable => false,
+                        DirectiveKind::DisableLine => warning.start_position.line == lines[position],
+                        DirectiveKind::DisableNextLine => warning.start_position.line == lines[position] + 1,
+                    };
+
+                    if !is_in_scope || !directive.applies_to(&warning.rule) {
+                        continue;
+                    }
+
+                    let rule = directive.rules.iter().position(|rule| *rule == warning.rule).unwrap_or(0);
+
+                    used[position][rule] = true;
+                    is_suppressed = true;
+                }
+
+                !is_suppressed
+            })
+            .collect::<Vec<LinterWarning>>();
+
+        if !report_unused {
+            return kept;
+        }
+
+        for (position, directive) in self.directives.iter().enumerate() {
+            if directive.kind == DirectiveKind::Enable {
+                continue;
+            }
+
+            let message = if directive.rules.is_empty() {
+                if used[position][0] {
+                    continue;
+                }
+
+                "Unused suppression: no warning is raised here.".to_string()
+            } else {
+                let unused = directive
+                    .rules
+                    .iter()
+                    .zip(&used[position])
+                    .filter(|(_, is_used)| !**is_used)
+                    .map(|(rule, _)| format!("`{}`", rule))
+                    .collect::<Vec<String>>();
+
+                if unused.is_empty() {
+                    continue;
+                }
+
+                format!("Unused suppression: no {} warning is raised here.", unused.join(", "))
+            };
+
+            let mut warning = LinterWarning {
+                rule: UNUSED_SUPPRESSION_RULE.to_string(),
+                message,
+                start: directive.start,
+                end: directive.end,
+                ..Default::default()
+            };
+
+            warning.locate(index);
+            kept.push(warning);
+        }
+
+        kept
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use errors::Position;
+
+    fn warning(text: &str, rule: &str, start: usize) -> LinterWarning {
+        let mut warning = LinterWarning { rule: rule.to_string(), start, end: start + 1, ..Default::default() };
+
+        warning.locate(&LineIndex::new(text));
+        warning
+    }
+
+    fn rules(warnings: &[LinterWarning]) -> Vec<&str> {
+        warnings.iter().map(|warning| warning.rule.as_str()).collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let suppressions = Suppressions::parse(
+            "<!-- typolint-disable-next-line quotes, price -- quoting a tweet -->\n\
+             % typolint-disable\n\
+             The typolint-disable directive.\n\
+             # typolint-enable\n",
+        );
+
+        assert_eq!(3, suppressions.directives.len());
+        assert_eq!(DirectiveKind::DisableNextLine, suppressions.directives[0].kind);
+        assert_eq!(vec!["quotes", "price"], suppressions.directives[0].rules);
+        assert_eq!(suppressions.directives[2].start, suppressions.directives[1].block_end);
+    }
+
+    #[test]
+    fn test_apply() {
+        let text = "A\n<!-- typolint-disable-next-line quotes -->\n\"B\"\n\"C\" <!-- typolint-disable-line -->\n\
+                    <!-- typolint-disable price -->\n\"D\" 10 €\n<!-- typolint-enable -->\n10 €\n";
+        let suppressions = Suppressions::parse(text);
+        let index = LineIndex::new(text);
+
+        let warnings = vec![
+            warning(text, "quotes", text.find("\"B").unwrap()),
+            warning(text, "quotes", text.find("\"C").unwrap()),
+            warning(text, "quotes", text.find("\"D").unwrap()),
+            warning(text, "price", text.find("10").unwrap()),
+            warning(text, "price", text.rfind("10").unwrap()),
+        ];
+
+        let warnings = suppressions.apply(warnings, &index, true);
+
+        assert_eq!(vec!["quotes", "price"], rules(&warnings));
+        assert_eq!(6, warnings[0].start_position.line);
+        assert_eq!(8, warnings[1].start_position.line);
+    }
+
+    #[test]
+    fn test_apply_reports_unused_suppressions() {
+        let text = "<!-- typolint-disable-next-line quotes, price -->\n\"A\"\n<!-- typolint-disable-line -->\n";
+        let suppressions = Suppressions::parse(text);
+        let index = LineIndex::new(text);
+
+        let warnings = suppressions.apply(vec![warning(text, "quotes", text.find('"').unwrap())], &index, true);
+
+        assert_eq!(vec![UNUSED_SUPPRESSION_RULE, UNUSED_SUPPRESSION_RULE], rules(&warnings));
+        assert_eq!("Unused suppression: no `price` warning is raised here.", warnings[0].message);
+        assert_eq!(Position { line: 1, column: 1, utf16_column: 1 }, warnings[0].start_position);
+        assert_eq!("Unused suppression: no warning is raised here.", warnings[1].message);
+
+        assert!(suppressions.apply(Vec::new(), &index, false).is_empty());
+    }
+}